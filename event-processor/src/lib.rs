@@ -1,4 +1,5 @@
 use allocative::Allocative;
+use regex::Regex;
 use starlark::any::ProvidesStaticType;
 use starlark::environment::{GlobalsBuilder, Module};
 use starlark::eval::Evaluator;
@@ -7,31 +8,94 @@ use starlark::values::none::NoneType;
 use starlark::values::{starlark_value, Heap, NoSerialize, StarlarkValue, Value};
 use starlark::{starlark_module, starlark_simple_value};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
 pub fn evaluate_rule(content: &str) -> starlark::Result<()> {
+    let emitted = evaluate(content, None)?;
+    for emitted in &emitted {
+        println!("emitted: {emitted}");
+    }
+    Ok(())
+}
+
+/// Evaluates a script converted from a configuration, calling its `evaluate(event)` with the
+/// event, a dict of its `name` and webhook `payload`, and returns the emitted JSON results.
+pub fn evaluate_event(content: &str, event: &serde_json::Value) -> starlark::Result<Vec<String>> {
+    evaluate(content, Some(event))
+}
+
+fn evaluate(content: &str, event: Option<&serde_json::Value>) -> starlark::Result<Vec<String>> {
     let ast = AstModule::parse("rule.star", content.to_owned(), &Dialect::Standard)?;
-    let globals = GlobalsBuilder::new().with(starlark_fetch).build();
+    let globals = GlobalsBuilder::standard()
+        .with(starlark_fetch)
+        .with(starlark_regex)
+        .build();
     let module = Module::new();
     let store = Store::default();
     {
         let mut eval = Evaluator::new(&module);
         eval.extra = Some(&store);
         let _ = eval.eval_module(ast, &globals)?;
+        if let Some(event) = event {
+            let evaluate = module.get("evaluate").ok_or_else(|| {
+                starlark::Error::new_other(anyhow::anyhow!(
+                    "the script doesn't define `evaluate(event)`"
+                ))
+            })?;
+            let event = module.heap().alloc(event);
+            let _ = eval.eval_function(evaluate, &[event], &[])?;
+        }
     }
-    for emitted in &*store.0.borrow() {
-        println!("emitted: {emitted}");
-    }
-    Ok(())
+    Ok(store.emitted.into_inner())
 }
 
-// Define a store in which to accumulate JSON strings
+// Define a store in which to accumulate JSON strings, and the regexes compiled by `matches`
 #[derive(Debug, ProvidesStaticType, Default)]
-struct Store(RefCell<Vec<String>>);
+struct Store {
+    emitted: RefCell<Vec<String>>,
+    regexes: RefCell<HashMap<String, Matcher>>,
+}
 
 impl Store {
     fn add(&self, x: String) {
-        self.0.borrow_mut().push(x)
+        self.emitted.borrow_mut().push(x)
+    }
+
+    /// Whether the regex matches the value, compiling the regex on its first use only.
+    fn matches(&self, regex: &str, value: &str) -> starlark::Result<bool> {
+        if let Some(matcher) = self.regexes.borrow().get(regex) {
+            return Ok(matcher.is_match(value));
+        }
+        let matcher = Matcher::new(regex)?;
+        let matched = matcher.is_match(value);
+        self.regexes.borrow_mut().insert(regex.to_owned(), matcher);
+        Ok(matched)
+    }
+}
+
+/// A regex of `matches`, compiled by the `regex` crate unless it needs backtracking.
+#[derive(Debug)]
+enum Matcher {
+    Fast(Regex),
+    Backtracking(fancy_regex::Regex),
+}
+
+impl Matcher {
+    fn new(regex: &str) -> starlark::Result<Self> {
+        if let Ok(regex) = Regex::new(regex) {
+            return Ok(Self::Fast(regex));
+        }
+        fancy_regex::Regex::new(regex)
+            .map(Self::Backtracking)
+            .map_err(starlark::Error::new_value)
+    }
+
+    fn is_match(&self, value: &str) -> bool {
+        match self {
+            Self::Fast(regex) => regex.is_match(value),
+            Self::Backtracking(regex) => regex.is_match(value).unwrap_or(false),
+        }
     }
 }
 
@@ -52,6 +116,21 @@ fn starlark_fetch(builder: &mut GlobalsBuilder) {
     }
 }
 
+#[starlark_module]
+fn starlark_regex(builder: &mut GlobalsBuilder) {
+    /// Whether the regex matches the value, flags are given inline like `(?i)^wip`.
+    ///
+    /// A regex with lookarounds or backreferences is matched by backtracking, and doesn't match
+    /// when it exceeds the backtrack limit.
+    fn matches(regex: &str, value: &str, eval: &mut Evaluator) -> starlark::Result<bool> {
+        eval.extra
+            .unwrap()
+            .downcast_ref::<Store>()
+            .unwrap()
+            .matches(regex, value)
+    }
+}
+
 #[derive(Debug, Clone, ProvidesStaticType, NoSerialize, Allocative)]
 pub struct Changeset {
     pub repository: String,
@@ -79,7 +158,7 @@ impl<'v> StarlarkValue<'v> for Changeset {
 mod tests {
     use indoc::indoc;
 
-    use crate::evaluate_rule;
+    use crate::{evaluate_event, evaluate_rule};
 
     #[test]
     fn it_works() -> starlark::Result<()> {
@@ -92,4 +171,35 @@ mod tests {
         "};
         evaluate_rule(content)
     }
+
+    #[test]
+    fn events_are_passed_to_evaluate() -> starlark::Result<()> {
+        let content = indoc! {r#"
+            def evaluate(event):
+                emit({"name": event["name"], "draft": event["payload"]["draft"]})
+        "#};
+        let event = serde_json::json!({"name": "pull_request.opened", "payload": {"draft": true}});
+
+        let emitted = evaluate_event(content, &event)?;
+
+        assert_eq!(emitted, [r#"{"name":"pull_request.opened","draft":true}"#]);
+        assert!(evaluate_event("x = 1", &event).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn regexes_are_matched_by_either_engine() -> starlark::Result<()> {
+        let content = indoc! {r#"
+            def evaluate(event):
+                for title in event["payload"]["titles"]:
+                    emit([matches("(?i)^wip", title), matches("(?<=feature/)x", title)])
+        "#};
+        let event = serde_json::json!({"payload": {"titles": ["WIP: x", "feature/x"]}});
+
+        let emitted = evaluate_event(content, &event)?;
+
+        assert_eq!(emitted, ["[true,false]", "[false,true]"]);
+        assert!(evaluate_event(r#"matches("(wip", "wip")"#, &event).is_err());
+        Ok(())
+    }
 }
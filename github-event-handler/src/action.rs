@@ -0,0 +1,22 @@
+pub mod assign;
+pub mod checks;
+pub mod close;
+pub mod comment;
+pub mod labels;
+pub mod merge;
+pub mod plan;
+pub mod review;
+
+use snafu::Snafu;
+
+#[derive(Debug, Snafu)]
+pub enum ActionError {
+    #[snafu(display("Unable to query the GitHub API: {source}"))]
+    Api {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Unable to query the evaluation context: {source}"))]
+    Context {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
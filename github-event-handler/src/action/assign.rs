@@ -0,0 +1,27 @@
+use crate::action::{ActionError, ApiSnafu};
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::action::Assignees;
+use octocrab::models::Repository;
+use snafu::ResultExt;
+use tracing::instrument;
+
+/// Assigns the planned logins which aren't assigned yet in one request, returning the newly
+/// assigned ones.
+#[instrument(skip(api, repository, planned), fields(repo = %repository.name))]
+pub async fn assign<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    author: &str,
+    assigned: &[String],
+    planned: &Assignees,
+) -> Result<Vec<String>, ActionError> {
+    let assignees = planned.resolve(author, assigned);
+    if !assignees.is_empty() {
+        api.add_assignees(repository, number, &assignees)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+    }
+    Ok(assignees)
+}
@@ -0,0 +1,30 @@
+use crate::action::{ActionError, ApiSnafu};
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::action::CheckRun;
+use octocrab::models::Repository;
+use snafu::ResultExt;
+use tracing::instrument;
+
+/// Reports the outcome of the rule as a check run on the head of the pull request.
+///
+/// Every rule has a check run of its own, named after the rule, GitHub shows the latest one for
+/// the commit.
+#[instrument(skip(api, repository, check_run), fields(repo = %repository.name))]
+pub async fn checks<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    head_sha: &str,
+    key: &str,
+    check_run: &CheckRun,
+) -> Result<(), ActionError> {
+    api.create_check_run(
+        repository,
+        head_sha,
+        &format!("Mergeable: {key}"),
+        check_run,
+    )
+    .await
+    .map_err(|err| Box::new(err) as _)
+    .context(ApiSnafu)?;
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::action::{ActionError, ApiSnafu};
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::action::Close;
+use octocrab::models::{IssueState, Repository};
+use snafu::ResultExt;
+use tracing::instrument;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseResult {
+    Closed,
+    /// Closed in the meantime, by somebody else or an earlier run.
+    AlreadyClosed,
+}
+
+/// Closes the pull request or issue, leaving the closing comment first.
+#[instrument(skip(api, repository, close), fields(repo = %repository.name))]
+pub async fn close<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    close: &Close,
+) -> Result<CloseResult, ActionError> {
+    let issue = api
+        .get_issue(repository, number)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    if issue.state == IssueState::Closed {
+        return Ok(CloseResult::AlreadyClosed);
+    }
+    if let Some(comment) = &close.comment {
+        api.create_issue_comment(repository, number, comment)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+    }
+    api.close_issue(repository, number, close.state_reason)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    Ok(CloseResult::Closed)
+}
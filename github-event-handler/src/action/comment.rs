@@ -0,0 +1,163 @@
+use crate::action::{ActionError, ApiSnafu};
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::action::Comment;
+use octocrab::models::issues::Comment as IssueComment;
+use octocrab::models::Repository;
+use snafu::ResultExt;
+use tracing::instrument;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentResult {
+    Created,
+    Updated,
+    /// The latest comment already says the same.
+    Unchanged,
+}
+
+/// Identifies the comments of a rule, invisible in the rendered markdown.
+fn marker(key: &str) -> String {
+    format!("<!-- mergeable-comment: {key} -->")
+}
+
+/// Comments on the pull request or issue, replacing the previous comment of the same rule.
+///
+/// With `leave_old_comment` the previous comments are kept and a new one is added, unless the
+/// latest one is identical.
+#[instrument(skip(api, repository, comment), fields(repo = %repository.name))]
+pub async fn comment<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    key: &str,
+    comment: &Comment,
+) -> Result<CommentResult, ActionError> {
+    let marker = marker(key);
+    let body = format!("{marker}\n{}", comment.body);
+    let previous = previous(api, repository, number, &marker).await?;
+    let Some((latest, older)) = previous.split_last() else {
+        create(api, repository, number, &body).await?;
+        return Ok(CommentResult::Created);
+    };
+    if comment.leave_old_comment && latest.body.as_deref() != Some(body.as_str()) {
+        create(api, repository, number, &body).await?;
+        return Ok(CommentResult::Created);
+    }
+    replace(api, repository, latest, older, &body).await
+}
+
+/// Replaces the previous comment of the same rule, `None` if there is none to replace.
+#[instrument(skip(api, repository, body), fields(repo = %repository.name))]
+pub async fn update_comment<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    key: &str,
+    body: &str,
+) -> Result<Option<CommentResult>, ActionError> {
+    let marker = marker(key);
+    let body = format!("{marker}\n{body}");
+    let previous = previous(api, repository, number, &marker).await?;
+    let Some((latest, older)) = previous.split_last() else {
+        return Ok(None);
+    };
+    replace(api, repository, latest, older, &body)
+        .await
+        .map(Some)
+}
+
+/// The comments of this app with the marker, the latest one last.
+async fn previous<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    marker: &str,
+) -> Result<Vec<IssueComment>, ActionError> {
+    let comments = api
+        .list_issue_comments(repository, number)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    let bot = api
+        .bot_login()
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    // anybody could quote the marker, even other bots, only our own comments are ours to manage
+    Ok(comments
+        .into_iter()
+        .filter(|comment| comment.user.login == bot)
+        .filter(|comment| {
+            comment
+                .body
+                .as_ref()
+                .is_some_and(|body| body.starts_with(marker))
+        })
+        .collect())
+}
+
+/// Updates the latest comment and deletes the older ones.
+async fn replace<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    latest: &IssueComment,
+    older: &[IssueComment],
+    body: &str,
+) -> Result<CommentResult, ActionError> {
+    if latest.body.as_deref() == Some(body) {
+        return Ok(CommentResult::Unchanged);
+    }
+    for outdated in older {
+        api.delete_issue_comment(repository, outdated.id)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+    }
+    api.update_issue_comment(repository, latest.id, body)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    Ok(CommentResult::Updated)
+}
+
+async fn create<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    body: &str,
+) -> Result<(), ActionError> {
+    api.create_issue_comment(repository, number, body)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::comment::{comment, CommentResult};
+    use crate::fake::{self, repository, FakeApi, BOT_LOGIN};
+    use mergeable_compatibility_layer::action::Comment;
+
+    #[tokio::test]
+    async fn only_the_comments_of_this_app_are_replaced() {
+        let marker = "<!-- mergeable-comment: ready -->";
+        let api = FakeApi {
+            comments: vec![
+                fake::comment(1, BOT_LOGIN, &format!("{marker}\nold")),
+                fake::comment(2, "other-app[bot]", &format!("{marker}\nquoted")),
+                fake::comment(3, "octocat", &format!("{marker}\nquoted")),
+            ],
+            ..Default::default()
+        };
+        let planned = Comment {
+            body: "new".into(),
+            leave_old_comment: false,
+        };
+
+        let result = comment(&api, &repository("repo"), 1, "ready", &planned)
+            .await
+            .unwrap();
+
+        assert_eq!(result, CommentResult::Updated);
+        assert_eq!(api.calls(), [format!("update comment 1: {marker}\nnew")]);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::action::{ActionError, ApiSnafu};
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::action::{LabelChange, LabelDiff};
+use octocrab::models::Repository;
+use snafu::ResultExt;
+use tracing::{info, instrument};
+
+/// Applies the label changes of every rule triggered by an event at once.
+///
+/// Only the net difference to the current labels is sent, so rules adding and deleting the same
+/// label don't fight each other. Labels missing in the repository are skipped, unless one of the
+/// actions asks for them to be created.
+#[instrument(skip(api, repository, changes), fields(repo = %repository.name))]
+pub async fn labels<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    changes: &[LabelChange],
+) -> Result<LabelDiff, ActionError> {
+    let current = api
+        .list_issue_labels(repository, number)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    let mut diff = LabelDiff::resolve(&current, changes);
+    if !diff.add.is_empty() {
+        let existing = api
+            .list_repository_labels(repository)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+        let mut missing = Vec::new();
+        for label in &diff.add {
+            if existing
+                .iter()
+                .any(|other| other.eq_ignore_ascii_case(label))
+            {
+                continue;
+            }
+            match changes.iter().find_map(|change| change.color_of(label)) {
+                Some(color) => api
+                    .create_label(repository, label, color)
+                    .await
+                    .map_err(|err| Box::new(err) as _)
+                    .context(ApiSnafu)?,
+                None => {
+                    info!(label, "skipping label missing in the repository");
+                    missing.push(label.clone());
+                }
+            }
+        }
+        diff.add.retain(|label| !missing.contains(label));
+    }
+    if !diff.add.is_empty() {
+        api.add_issue_labels(repository, number, &diff.add)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+    }
+    for label in &diff.remove {
+        api.remove_issue_label(repository, number, label)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+    }
+    Ok(diff)
+}
@@ -0,0 +1,172 @@
+use crate::action::{ActionError, ApiSnafu};
+use crate::api::{GitHubApi, MergeOutcome};
+use mergeable_compatibility_layer::action::MergeCommit;
+use octocrab::models::pulls::MergeableState;
+use octocrab::models::{IssueState, Repository};
+use snafu::ResultExt;
+use std::time::Duration;
+use tracing::{instrument, Instrument};
+
+/// How often GitHub is asked again while it computes mergeability or another merge landed.
+const ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeResult {
+    Merged,
+    AlreadyMerged,
+    /// The pull request isn't safe to merge, it's left as is.
+    Skipped {
+        reason: String,
+    },
+    /// GitHub is still computing the mergeability or another merge landed, the merge is tried
+    /// again in the background.
+    Retrying,
+}
+
+/// Merges the pull request if it is still at the evaluated `sha` and GitHub considers it ready.
+///
+/// A pull request is ready when it has no conflicts and its required checks and reviews are
+/// satisfied, failing checks which aren't required don't prevent the merge. Only the first
+/// attempt is made right away, the event doesn't wait for the delays between the retries.
+#[instrument(skip(api, repository, commit), fields(repo = %repository.name))]
+pub async fn merge<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    sha: &str,
+    commit: &MergeCommit,
+) -> Result<MergeResult, ActionError> {
+    if let Some(result) = attempt(api, repository, number, sha, commit).await? {
+        return Ok(result);
+    }
+    let (api, repository, sha, commit) = (
+        api.clone(),
+        repository.clone(),
+        sha.to_owned(),
+        commit.clone(),
+    );
+    let retries = async move {
+        match retry(&api, &repository, number, &sha, &commit).await {
+            Ok(result) => tracing::info!(?result, "merge"),
+            Err(err) => tracing::warn!(%err, "unable to merge the pull request"),
+        }
+    };
+    tokio::spawn(retries.in_current_span());
+    Ok(MergeResult::Retrying)
+}
+
+/// The remaining attempts after the first one, each after a longer delay.
+async fn retry<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    sha: &str,
+    commit: &MergeCommit,
+) -> Result<MergeResult, ActionError> {
+    for attempt_number in 2..=ATTEMPTS {
+        tokio::time::sleep(RETRY_DELAY * (attempt_number - 1)).await;
+        if let Some(result) = attempt(api, repository, number, sha, commit).await? {
+            return Ok(result);
+        }
+        tracing::debug!(attempt_number, "the pull request is not ready yet");
+    }
+    Ok(skip("the pull request did not become ready to merge"))
+}
+
+/// Tries to merge the pull request once, `None` if it's worth trying again.
+async fn attempt<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    number: u64,
+    sha: &str,
+    commit: &MergeCommit,
+) -> Result<Option<MergeResult>, ActionError> {
+    let pull_request = api
+        .get_pull_request(repository, number)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    if pull_request.merged_at.is_some() {
+        return Ok(Some(MergeResult::AlreadyMerged));
+    }
+    if pull_request.state == Some(IssueState::Closed) {
+        return Ok(Some(skip("the pull request is closed")));
+    }
+    if pull_request.head.sha != sha {
+        return Ok(Some(skip(
+            "the head changed since the pull request was evaluated",
+        )));
+    }
+    match pull_request.mergeable {
+        // GitHub computes the mergeability in the background after every push
+        None => return Ok(None),
+        Some(false) => return Ok(Some(skip("the pull request has conflicts"))),
+        Some(true) => {}
+    }
+    let reason = match pull_request.mergeable_state {
+        Some(MergeableState::Clean | MergeableState::Unstable | MergeableState::HasHooks) => None,
+        Some(MergeableState::Blocked) => Some("required checks or reviews are not satisfied"),
+        Some(MergeableState::Behind) => Some("the head is behind the base branch"),
+        Some(MergeableState::Dirty) => Some("the pull request has conflicts"),
+        Some(MergeableState::Draft) => Some("the pull request is a draft"),
+        _ => return Ok(None),
+    };
+    if let Some(reason) = reason {
+        return Ok(Some(skip(reason)));
+    }
+    let outcome = api
+        .merge_pull_request(repository, number, sha, commit)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    Ok(match outcome {
+        MergeOutcome::Merged => Some(MergeResult::Merged),
+        MergeOutcome::BaseBranchModified => {
+            tracing::debug!("base branch was modified, retrying the merge");
+            None
+        }
+        MergeOutcome::HeadBranchModified => Some(skip(
+            "the head changed since the pull request was evaluated",
+        )),
+        MergeOutcome::Rejected { reason } => Some(skip(&reason)),
+    })
+}
+
+fn skip(reason: &str) -> MergeResult {
+    tracing::info!(reason, "not merging the pull request");
+    MergeResult::Skipped {
+        reason: reason.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::merge::{merge, MergeResult, ATTEMPTS};
+    use crate::fake::{pull_request, repository, FakeApi};
+    use mergeable_compatibility_layer::action::{MergeCommit, MergeMethod};
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn merges_are_retried_in_the_background() {
+        let api = FakeApi {
+            pull_requests: vec![pull_request(1, "abc", None)],
+            ..Default::default()
+        };
+        let commit = MergeCommit {
+            method: MergeMethod::Squash,
+            title: None,
+            message: None,
+        };
+
+        let result = merge(&api, &repository("repo"), 1, "abc", &commit)
+            .await
+            .unwrap();
+
+        assert_eq!(result, MergeResult::Retrying);
+        assert_eq!(api.calls(), ["get #1"]);
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        // GitHub never computed the mergeability, so the pull request is left as is
+        assert_eq!(api.calls().len(), ATTEMPTS as usize - 1);
+    }
+}
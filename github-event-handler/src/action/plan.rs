@@ -0,0 +1,119 @@
+use crate::action::assign::assign;
+use crate::action::checks::checks;
+use crate::action::close::close;
+use crate::action::comment::comment;
+use crate::action::labels::labels;
+use crate::action::merge::merge;
+use crate::action::review::request_review;
+use crate::action::{ActionError, ApiSnafu};
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::action::Plan;
+use mergeable_compatibility_layer::evaluation::EvaluationContext;
+use octocrab::models::pulls::PullRequest;
+use octocrab::models::Repository;
+use snafu::ResultExt;
+use tracing::instrument;
+
+/// The pull request or issue a plan is executed on.
+#[derive(Debug, Clone, Copy)]
+pub struct Target<'a> {
+    pub number: u64,
+    /// Merging and requesting reviews require the pull request as it was evaluated.
+    pub pull_request: Option<&'a PullRequest>,
+}
+
+/// Executes the plan, comments and labels first, then assignments and check runs, then closing or
+/// merging.
+///
+/// A failing action doesn't stop the remaining ones, the first failure is returned after all of
+/// them have been attempted.
+#[instrument(skip_all, fields(repo = %repository.name, number = target.number))]
+pub async fn execute<A: GitHubApi, C: EvaluationContext>(
+    api: &A,
+    context: &C,
+    repository: &Repository,
+    target: Target<'_>,
+    plan: &Plan,
+) -> Result<(), ActionError> {
+    for conflict in &plan.conflicts {
+        tracing::warn!(?conflict, "conflicting actions");
+    }
+    if plan.is_empty() {
+        return Ok(());
+    }
+    tracing::info!(?plan, "executing the action plan");
+    let number = target.number;
+    let mut failures = Vec::new();
+    for (key, planned) in &plan.comments {
+        failures.extend(comment(api, repository, number, key, planned).await.err());
+    }
+    if !plan.labels.is_empty() {
+        failures.extend(labels(api, repository, number, &plan.labels).await.err());
+    }
+    if !plan.assignees.is_empty() {
+        let assigned = async {
+            let issue = api
+                .get_issue(repository, number)
+                .await
+                .map_err(|err| Box::new(err) as _)
+                .context(ApiSnafu)?;
+            let assignees: Vec<_> = issue.assignees.into_iter().map(|user| user.login).collect();
+            assign(
+                api,
+                repository,
+                number,
+                &issue.user.login,
+                &assignees,
+                &plan.assignees,
+            )
+            .await
+        };
+        failures.extend(assigned.await.err());
+    }
+    match target.pull_request {
+        Some(pull_request) => {
+            for (key, check_run) in &plan.checks {
+                let head_sha = &pull_request.head.sha;
+                failures.extend(
+                    checks(api, repository, head_sha, key, check_run)
+                        .await
+                        .err(),
+                );
+            }
+            if !plan.reviewers.is_empty() {
+                failures.extend(
+                    request_review(api, context, repository, pull_request, &plan.reviewers)
+                        .await
+                        .err(),
+                );
+            }
+            if let Some(commit) = &plan.merge {
+                let merged = merge(api, repository, number, &pull_request.head.sha, commit).await;
+                match merged {
+                    Ok(result) => tracing::info!(?result, "merge"),
+                    Err(err) => failures.push(err),
+                }
+            }
+        }
+        None if !plan.checks.is_empty() || !plan.reviewers.is_empty() || plan.merge.is_some() => {
+            tracing::info!("skipping checks, reviews and merging, the target is no pull request");
+        }
+        None => {}
+    }
+    if let Some(planned) = &plan.close {
+        match close(api, repository, number, planned).await {
+            Ok(result) => tracing::info!(?result, "close"),
+            Err(err) => failures.push(err),
+        }
+    }
+    let mut failures = failures.into_iter();
+    match failures.next() {
+        Some(first) => {
+            for err in failures {
+                tracing::warn!(%err, "action failed");
+            }
+            Err(first)
+        }
+        None => Ok(()),
+    }
+}
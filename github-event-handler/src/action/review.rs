@@ -0,0 +1,147 @@
+use crate::action::{ActionError, ApiSnafu, ContextSnafu};
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::action::{ReviewRequest, ReviewerSelection, Reviewers};
+use mergeable_compatibility_layer::evaluation::EvaluationContext;
+use octocrab::models::pulls::PullRequest;
+use octocrab::models::Repository;
+use snafu::ResultExt;
+use std::collections::BTreeMap;
+use tracing::instrument;
+
+/// Requests the planned reviewers and teams, and a selection of the members of the other teams,
+/// in one request.
+///
+/// The teams are expanded through the `context`, which caches their members, and the load of a
+/// member is the number of open pull requests awaiting their review across all repositories of
+/// the owner, not only this one.
+#[instrument(skip_all, fields(repo = %repository.name, number = pull_request.number))]
+pub async fn request_review<A: GitHubApi, C: EvaluationContext>(
+    api: &A,
+    context: &C,
+    repository: &Repository,
+    pull_request: &PullRequest,
+    reviewers: &Reviewers,
+) -> Result<ReviewRequest, ActionError> {
+    let author = pull_request
+        .user
+        .as_ref()
+        .map(|user| user.login.as_str())
+        .unwrap_or_default();
+    let requested: Vec<_> = pull_request
+        .requested_reviewers
+        .iter()
+        .flatten()
+        .map(|reviewer| reviewer.login.clone())
+        .collect();
+    let requested_teams: Vec<_> = pull_request
+        .requested_teams
+        .iter()
+        .flatten()
+        .map(|team| team.slug.clone())
+        .collect();
+    let mut request = reviewers.request(author, &requested, &requested_teams);
+    let mut load = BTreeMap::new();
+    for team in reviewers.selected_teams() {
+        let members = context
+            .team_members(&team.team)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ContextSnafu)?;
+        if team.selection == ReviewerSelection::LeastLoaded {
+            // the search of the context is scoped to the repository, the load isn't
+            let owner = owner(repository)
+                .map(|owner| format!("user:{owner} "))
+                .unwrap_or_default();
+            for member in &members {
+                if load.contains_key(member) {
+                    continue;
+                }
+                let open = api
+                    .search_issues(&format!("{owner}is:pr is:open review-requested:{member}"))
+                    .await
+                    .map_err(|err| Box::new(err) as _)
+                    .context(ApiSnafu)?;
+                load.insert(member.clone(), open.len());
+            }
+        }
+        request.select(team, &members, pull_request.number, &load);
+    }
+    if !request.is_empty() {
+        api.request_reviewers(
+            repository,
+            pull_request.number,
+            &request.reviewers,
+            &request.teams,
+        )
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)?;
+    }
+    Ok(request)
+}
+
+fn owner(repository: &Repository) -> Option<&str> {
+    match &repository.owner {
+        Some(owner) => Some(owner.login.as_str()),
+        None => repository
+            .full_name
+            .as_deref()
+            .and_then(|full_name| full_name.split_once('/'))
+            .map(|(owner, _)| owner),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::review::request_review;
+    use crate::context::EventContext;
+    use crate::fake::{pull_request, repository, FakeApi};
+    use crate::membership::MembershipCache;
+    use mergeable_compatibility_layer::action::Reviewers;
+    use mergeable_compatibility_layer::configuration::actions::Action;
+    use octocrab::models::InstallationId;
+
+    #[tokio::test]
+    async fn one_request_is_made_with_the_load_counted_across_the_owner() {
+        let api = FakeApi {
+            search_results: vec![4, 8],
+            ..Default::default()
+        };
+        let memberships = MembershipCache::default();
+        memberships.insert(
+            InstallationId(1),
+            "core",
+            vec!["bob".to_owned(), "alice".to_owned()],
+        );
+        let repository = repository("repo");
+        let pull_request = pull_request(1, "abc", None);
+        let payload = serde_json::Value::Null;
+        let context =
+            EventContext::new(&api, InstallationId(1), &memberships, &repository, &payload);
+        let mut reviewers = Reviewers::default();
+        for yaml in [
+            "{do: request_review, reviewers: [carol], teams: [core], selection: least_loaded}",
+            "{do: request_review, reviewers: [Carol], teams: [docs]}",
+        ] {
+            let Action::RequestReview(action) = serde_yaml::from_str(yaml).unwrap() else {
+                panic!("not a request_review action");
+            };
+            reviewers.add(&action);
+        }
+
+        let request = request_review(&api, &context, &repository, &pull_request, &reviewers)
+            .await
+            .unwrap();
+
+        assert_eq!(request.reviewers, ["carol", "alice"]);
+        assert_eq!(request.teams, ["docs"]);
+        assert_eq!(
+            api.calls(),
+            [
+                "search user:octo is:pr is:open review-requested:bob",
+                "search user:octo is:pr is:open review-requested:alice",
+                "request reviews #1: [\"carol\", \"alice\"] [\"docs\"]",
+            ]
+        );
+    }
+}
@@ -1,74 +1,942 @@
-use octocrab::models::{CheckRunId, Repository};
-use octocrab::params::checks::{
-    CheckRunConclusion, CheckRunOutput, CheckRunOutputAnnotation, CheckRunOutputAnnotationLevel,
-    CheckRunStatus,
+use hyper::StatusCode;
+use mergeable_compatibility_layer::action::{
+    CheckRun, CheckStatus, MergeCommit, MergeMethod, StateReason,
 };
-use octocrab::Octocrab;
+use octocrab::commits::PullRequestTarget;
+use octocrab::models::issues::{Comment, Issue, IssueStateReason};
+use octocrab::models::pulls::PullRequest;
+use octocrab::models::repos::DiffEntry;
+use octocrab::models::{CheckRunId, CommentId, IssueState, Repository};
+use octocrab::params::checks::{CheckRunConclusion, CheckRunOutput, CheckRunStatus};
+use octocrab::{params, Octocrab};
+use serde::{Deserialize, Serialize};
 use snafu::{Backtrace, ResultExt, Snafu};
-use std::fmt::Debug;
 use std::future::Future;
+use std::sync::OnceLock;
 use tracing::instrument;
 
-pub trait GitHubApi: Send {
-    fn create_commit_status(
+/// Cloned for the work which continues after the event was answered, like retried merges.
+pub trait GitHubApi: Clone + Send + Sync + 'static {
+    /// Creates a completed check run on the commit.
+    fn create_check_run(
         &self,
         repository: &Repository,
+        head_sha: &str,
+        name: &str,
+        check_run: &CheckRun,
+    ) -> impl Future<Output = Result<CheckRunId, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn list_pull_request_files(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> impl Future<Output = Result<Vec<DiffEntry>, impl std::error::Error + Send + Sync + 'static>>
+           + Send;
+
+    fn list_repository_topics(
+        &self,
+        repository: &Repository,
+    ) -> impl Future<Output = Result<Vec<String>, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn list_team_members(
+        &self,
+        org: &str,
+        team_slug: &str,
+    ) -> impl Future<Output = Result<Vec<String>, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    /// Names of the classic and Projects v2 boards an issue or pull request belongs to.
+    fn list_item_projects(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> impl Future<Output = Result<Vec<String>, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn get_pull_request(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> impl Future<Output = Result<PullRequest, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    /// The pull requests containing the commit, like the ones a status or check suite is about.
+    fn list_commit_pull_requests(
+        &self,
+        repository: &Repository,
+        sha: &str,
+    ) -> impl Future<Output = Result<Vec<PullRequest>, impl std::error::Error + Send + Sync + 'static>>
+           + Send;
+
+    /// Merges the pull request, as long as its head is still at `sha`.
+    fn merge_pull_request(
+        &self,
+        repository: &Repository,
+        number: u64,
         sha: &str,
-    ) -> impl Future<Output = Result<impl Debug, impl std::error::Error + Send + Sync + 'static>> + Send;
+        commit: &MergeCommit,
+    ) -> impl Future<Output = Result<MergeOutcome, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    /// The login of the bot the app acts as, like `mergeable[bot]`.
+    fn bot_login(
+        &self,
+    ) -> impl Future<Output = Result<String, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn list_issue_comments(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> impl Future<Output = Result<Vec<Comment>, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn create_issue_comment(
+        &self,
+        repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn update_issue_comment(
+        &self,
+        repository: &Repository,
+        comment: CommentId,
+        body: &str,
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn delete_issue_comment(
+        &self,
+        repository: &Repository,
+        comment: CommentId,
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn get_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> impl Future<Output = Result<Issue, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    /// Closes the issue or pull request, GitHub only records the reason for issues.
+    fn close_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        reason: StateReason,
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn request_reviewers(
+        &self,
+        repository: &Repository,
+        number: u64,
+        reviewers: &[String],
+        teams: &[String],
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn add_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        assignees: &[String],
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn list_issue_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> impl Future<Output = Result<Vec<String>, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn list_repository_labels(
+        &self,
+        repository: &Repository,
+    ) -> impl Future<Output = Result<Vec<String>, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn create_label(
+        &self,
+        repository: &Repository,
+        name: &str,
+        color: &str,
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    /// Adds the labels to the issue or pull request, GitHub creates missing ones on the fly.
+    fn add_issue_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+        labels: &[String],
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    fn remove_issue_label(
+        &self,
+        repository: &Repository,
+        number: u64,
+        label: &str,
+    ) -> impl Future<Output = Result<(), impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    /// A file on the default branch, `None` if it doesn't exist.
+    fn fetch_file(
+        &self,
+        repository: &Repository,
+        path: &str,
+    ) -> impl Future<
+        Output = Result<Option<RepositoryFile>, impl std::error::Error + Send + Sync + 'static>,
+    > + Send;
+
+    /// A file on the default branch of another repository, e.g. the organization's `.github`
+    /// repository, `None` if either doesn't exist.
+    fn fetch_repository_file(
+        &self,
+        owner: &str,
+        repository: &str,
+        path: &str,
+    ) -> impl Future<
+        Output = Result<Option<RepositoryFile>, impl std::error::Error + Send + Sync + 'static>,
+    > + Send;
+
+    /// A file at a branch or commit of the repository, `None` if it doesn't exist there.
+    fn fetch_file_at(
+        &self,
+        repository: &Repository,
+        path: &str,
+        reference: &str,
+    ) -> impl Future<
+        Output = Result<Option<RepositoryFile>, impl std::error::Error + Send + Sync + 'static>,
+    > + Send;
+
+    fn search_issues(
+        &self,
+        query: &str,
+    ) -> impl Future<Output = Result<Vec<Issue>, impl std::error::Error + Send + Sync + 'static>> + Send;
+
+    /// All repositories the installation has been granted access to.
+    fn list_installation_repositories(
+        &self,
+    ) -> impl Future<Output = Result<Vec<Repository>, impl std::error::Error + Send + Sync + 'static>>
+           + Send;
 }
 
 impl GitHubApi for Octocrab {
     #[allow(refining_impl_trait)]
-    #[instrument(skip(self, repository), fields(repo = %repository.name), ret)]
-    async fn create_commit_status(
+    #[instrument(skip(self, repository, check_run), fields(repo = %repository.name), ret)]
+    async fn create_check_run(
         &self,
         repository: &Repository,
-        sha: &str,
+        head_sha: &str,
+        name: &str,
+        check_run: &CheckRun,
     ) -> Result<CheckRunId, GitHubActionError> {
         let Some(owner) = repository.clone().owner else {
             return MissingOwnerSnafu.fail();
         };
+        let conclusion = match check_run.conclusion {
+            CheckStatus::Success => CheckRunConclusion::Success,
+            CheckStatus::Failure => CheckRunConclusion::Failure,
+            CheckStatus::Neutral => CheckRunConclusion::Neutral,
+            CheckStatus::Cancelled => CheckRunConclusion::Cancelled,
+            CheckStatus::TimedOut => CheckRunConclusion::TimedOut,
+            CheckStatus::ActionRequired => CheckRunConclusion::ActionRequired,
+        };
         self.checks(owner.login.to_owned(), repository.name.to_owned())
-            .create_check_run("my-check", sha)
-            .details_url("https://54aa-91-118-110-130.ngrok-free.app/1234")
-            .external_id("1234")
+            .create_check_run(name, head_sha)
             .status(CheckRunStatus::Completed)
-            .conclusion(CheckRunConclusion::Success)
+            .conclusion(conclusion)
             .output(CheckRunOutput {
-                title: "my title".to_string(),
-                summary: indoc::indoc! {"
-                    this **worked** right?
-                "}
-                .to_string(),
-                text: Some(
-                    indoc::indoc! {"
-                    # github yada
-
-                    > [!CAUTION]
-                    > Advises about risks or negative outcomes of certain actions.
-                "}
-                    .to_string(),
-                ),
-                annotations: vec![CheckRunOutputAnnotation {
-                    path: "Cargo.toml".to_string(),
-                    start_line: 5,
-                    end_line: 5,
-                    start_column: Some(8),
-                    end_column: Some(12),
-                    annotation_level: CheckRunOutputAnnotationLevel::Warning,
-                    message: "Is this **markdown**? insert meme here".to_string(),
-                    title: Some("invalid rule".into()),
-                    raw_details: Some("`yada` yada?".into()),
-                }],
+                title: check_run.title.clone(),
+                summary: check_run.summary.clone(),
+                text: Some(check_run.text.clone()),
+                annotations: vec![],
                 images: vec![],
             })
             .send()
             .await
             .context(OctocrabSnafu)
-            .map(|s| s.id)
+            .map(|check_run| check_run.id)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn list_pull_request_files(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<Vec<DiffEntry>, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let page = self
+            .pulls(owner.login.to_owned(), repository.name.to_owned())
+            .list_files(number)
+            .await
+            .context(OctocrabSnafu)?;
+        self.all_pages(page).await.context(OctocrabSnafu)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn list_repository_topics(
+        &self,
+        repository: &Repository,
+    ) -> Result<Vec<String>, GitHubActionError> {
+        #[derive(Deserialize)]
+        struct Topics {
+            names: Vec<String>,
+        }
+
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let route = format!(
+            "/repos/{owner}/{repo}/topics",
+            owner = owner.login,
+            repo = repository.name
+        );
+        let topics: Topics = self.get(route, None::<&()>).await.context(OctocrabSnafu)?;
+        Ok(topics.names)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self))]
+    async fn list_team_members(
+        &self,
+        org: &str,
+        team_slug: &str,
+    ) -> Result<Vec<String>, GitHubActionError> {
+        let page = self
+            .teams(org)
+            .members(team_slug)
+            .send()
+            .await
+            .context(OctocrabSnafu)?;
+        let members = self.all_pages(page).await.context(OctocrabSnafu)?;
+        Ok(members.into_iter().map(|member| member.login).collect())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn list_item_projects(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<Vec<String>, GitHubActionError> {
+        // classic projects are only reachable through their cards
+        const QUERY: &str = indoc::indoc! {"
+            query($owner: String!, $name: String!, $number: Int!) {
+              repository(owner: $owner, name: $name) {
+                issueOrPullRequest(number: $number) {
+                  ... on Issue {
+                    projectCards(first: 100) { nodes { project { name } } }
+                    projectItems(first: 100) { nodes { project { title } } }
+                  }
+                  ... on PullRequest {
+                    projectCards(first: 100) { nodes { project { name } } }
+                    projectItems(first: 100) { nodes { project { title } } }
+                  }
+                }
+              }
+            }
+        "};
+        #[derive(Deserialize)]
+        struct Response {
+            data: Option<Data>,
+            #[serde(default)]
+            errors: Vec<GraphQlError>,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            repository: Option<RepositoryNode>,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RepositoryNode {
+            issue_or_pull_request: Option<ItemNode>,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ItemNode {
+            project_cards: Option<Nodes<Card>>,
+            project_items: Option<Nodes<ProjectItem>>,
+        }
+        #[derive(Deserialize)]
+        struct Nodes<T> {
+            nodes: Vec<Option<T>>,
+        }
+        #[derive(Deserialize)]
+        struct Card {
+            project: ClassicProject,
+        }
+        #[derive(Deserialize)]
+        struct ClassicProject {
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct ProjectItem {
+            project: ProjectV2,
+        }
+        #[derive(Deserialize)]
+        struct ProjectV2 {
+            title: String,
+        }
+        #[derive(Deserialize)]
+        struct GraphQlError {
+            message: String,
+        }
+
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let response: Response = self
+            .graphql(&serde_json::json!({
+                "query": QUERY,
+                "variables": {
+                    "owner": owner.login,
+                    "name": repository.name,
+                    "number": number,
+                },
+            }))
+            .await
+            .context(OctocrabSnafu)?;
+        let Some(data) = response.data else {
+            let messages: Vec<_> = response.errors.into_iter().map(|err| err.message).collect();
+            return GraphQlSnafu {
+                message: messages.join(", "),
+            }
+            .fail();
+        };
+        let Some(item) = data
+            .repository
+            .and_then(|repository| repository.issue_or_pull_request)
+        else {
+            return Ok(Vec::new());
+        };
+        let classic = item
+            .project_cards
+            .into_iter()
+            .flat_map(|cards| cards.nodes)
+            .flatten()
+            .map(|card| card.project.name);
+        let v2 = item
+            .project_items
+            .into_iter()
+            .flat_map(|items| items.nodes)
+            .flatten()
+            .map(|item| item.project.title);
+        Ok(classic.chain(v2).collect())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn get_pull_request(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<PullRequest, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.pulls(owner.login.to_owned(), repository.name.to_owned())
+            .get(number)
+            .await
+            .context(OctocrabSnafu)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn list_commit_pull_requests(
+        &self,
+        repository: &Repository,
+        sha: &str,
+    ) -> Result<Vec<PullRequest>, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let page = self
+            .commits(owner.login.to_owned(), repository.name.to_owned())
+            .associated_pull_requests(PullRequestTarget::Sha(sha.to_owned()))
+            .per_page(100)
+            .send()
+            .await
+            .context(OctocrabSnafu)?;
+        self.all_pages(page).await.context(OctocrabSnafu)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository, commit), fields(repo = %repository.name))]
+    async fn merge_pull_request(
+        &self,
+        repository: &Repository,
+        number: u64,
+        sha: &str,
+        commit: &MergeCommit,
+    ) -> Result<MergeOutcome, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let pulls = self.pulls(owner.login.to_owned(), repository.name.to_owned());
+        let mut merge = pulls.merge(number).sha(sha).method(match commit.method {
+            MergeMethod::Merge => params::pulls::MergeMethod::Merge,
+            MergeMethod::Squash => params::pulls::MergeMethod::Squash,
+            MergeMethod::Rebase => params::pulls::MergeMethod::Rebase,
+        });
+        if let Some(title) = &commit.title {
+            merge = merge.title(title);
+        }
+        if let Some(message) = &commit.message {
+            merge = merge.message(message);
+        }
+        match merge.send().await {
+            Ok(merge) if merge.merged => Ok(MergeOutcome::Merged),
+            Ok(merge) => Ok(MergeOutcome::Rejected {
+                reason: merge.message.unwrap_or_default(),
+            }),
+            Err(octocrab::Error::GitHub { source, .. })
+                if source.status_code == StatusCode::METHOD_NOT_ALLOWED =>
+            {
+                if source.message.contains("Base branch was modified") {
+                    Ok(MergeOutcome::BaseBranchModified)
+                } else {
+                    Ok(MergeOutcome::Rejected {
+                        reason: source.message,
+                    })
+                }
+            }
+            Err(octocrab::Error::GitHub { source, .. })
+                if source.status_code == StatusCode::CONFLICT =>
+            {
+                Ok(MergeOutcome::HeadBranchModified)
+            }
+            Err(err) => Err(err).context(OctocrabSnafu),
+        }
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self))]
+    async fn bot_login(&self) -> Result<String, GitHubActionError> {
+        #[derive(Deserialize)]
+        struct Response {
+            data: Option<Data>,
+            #[serde(default)]
+            errors: Vec<GraphQlError>,
+        }
+        #[derive(Deserialize)]
+        struct Data {
+            viewer: Viewer,
+        }
+        #[derive(Deserialize)]
+        struct Viewer {
+            login: String,
+        }
+        #[derive(Deserialize)]
+        struct GraphQlError {
+            message: String,
+        }
+
+        // every installation acts as the same bot
+        static BOT_LOGIN: OnceLock<String> = OnceLock::new();
+        if let Some(login) = BOT_LOGIN.get() {
+            return Ok(login.clone());
+        }
+        let response: Response = self
+            .graphql(&serde_json::json!({ "query": "query { viewer { login } }" }))
+            .await
+            .context(OctocrabSnafu)?;
+        let Some(data) = response.data else {
+            let messages: Vec<_> = response.errors.into_iter().map(|err| err.message).collect();
+            return GraphQlSnafu {
+                message: messages.join(", "),
+            }
+            .fail();
+        };
+        Ok(BOT_LOGIN.get_or_init(|| data.viewer.login).clone())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn list_issue_comments(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<Vec<Comment>, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let page = self
+            .issues(owner.login.to_owned(), repository.name.to_owned())
+            .list_comments(number)
+            .per_page(100)
+            .send()
+            .await
+            .context(OctocrabSnafu)?;
+        self.all_pages(page).await.context(OctocrabSnafu)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository, body), fields(repo = %repository.name))]
+    async fn create_issue_comment(
+        &self,
+        repository: &Repository,
+        number: u64,
+        body: &str,
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .create_comment(number, body)
+            .await
+            .context(OctocrabSnafu)?;
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository, body), fields(repo = %repository.name))]
+    async fn update_issue_comment(
+        &self,
+        repository: &Repository,
+        comment: CommentId,
+        body: &str,
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .update_comment(comment, body)
+            .await
+            .context(OctocrabSnafu)?;
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn delete_issue_comment(
+        &self,
+        repository: &Repository,
+        comment: CommentId,
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .delete_comment(comment)
+            .await
+            .context(OctocrabSnafu)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn get_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<Issue, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .get(number)
+            .await
+            .context(OctocrabSnafu)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn close_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        reason: StateReason,
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let reason = match reason {
+            StateReason::Completed => IssueStateReason::Completed,
+            StateReason::NotPlanned => IssueStateReason::NotPlanned,
+        };
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .update(number)
+            .state(IssueState::Closed)
+            .state_reason(reason)
+            .send()
+            .await
+            .context(OctocrabSnafu)?;
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn request_reviewers(
+        &self,
+        repository: &Repository,
+        number: u64,
+        reviewers: &[String],
+        teams: &[String],
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.pulls(owner.login.to_owned(), repository.name.to_owned())
+            .request_reviews(number, reviewers, teams)
+            .await
+            .context(OctocrabSnafu)?;
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn add_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        assignees: &[String],
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let assignees: Vec<_> = assignees.iter().map(String::as_str).collect();
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .add_assignees(number, &assignees)
+            .await
+            .context(OctocrabSnafu)?;
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn list_issue_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<Vec<String>, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let page = self
+            .issues(owner.login.to_owned(), repository.name.to_owned())
+            .list_labels_for_issue(number)
+            .per_page(100)
+            .send()
+            .await
+            .context(OctocrabSnafu)?;
+        let labels = self.all_pages(page).await.context(OctocrabSnafu)?;
+        Ok(labels.into_iter().map(|label| label.name).collect())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn list_repository_labels(
+        &self,
+        repository: &Repository,
+    ) -> Result<Vec<String>, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let page = self
+            .issues(owner.login.to_owned(), repository.name.to_owned())
+            .list_labels_for_repo()
+            .per_page(100)
+            .send()
+            .await
+            .context(OctocrabSnafu)?;
+        let labels = self.all_pages(page).await.context(OctocrabSnafu)?;
+        Ok(labels.into_iter().map(|label| label.name).collect())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn create_label(
+        &self,
+        repository: &Repository,
+        name: &str,
+        color: &str,
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .create_label(name, color, "")
+            .await
+            .context(OctocrabSnafu)?;
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn add_issue_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+        labels: &[String],
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.issues(owner.login.to_owned(), repository.name.to_owned())
+            .add_labels(number, labels)
+            .await
+            .context(OctocrabSnafu)?;
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn remove_issue_label(
+        &self,
+        repository: &Repository,
+        number: u64,
+        label: &str,
+    ) -> Result<(), GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        let removed = self
+            .issues(owner.login.to_owned(), repository.name.to_owned())
+            .remove_label(number, label)
+            .await;
+        match removed {
+            Ok(_) => Ok(()),
+            // somebody else was faster, the label is gone either way
+            Err(octocrab::Error::GitHub { source, .. })
+                if source.status_code == StatusCode::NOT_FOUND =>
+            {
+                Ok(())
+            }
+            Err(err) => Err(err).context(OctocrabSnafu),
+        }
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn fetch_file(
+        &self,
+        repository: &Repository,
+        path: &str,
+    ) -> Result<Option<RepositoryFile>, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        self.fetch_repository_file(&owner.login, &repository.name, path)
+            .await
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self))]
+    async fn fetch_repository_file(
+        &self,
+        owner: &str,
+        repository: &str,
+        path: &str,
+    ) -> Result<Option<RepositoryFile>, GitHubActionError> {
+        fetch_content(self, owner, repository, path, None).await
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self, repository), fields(repo = %repository.name))]
+    async fn fetch_file_at(
+        &self,
+        repository: &Repository,
+        path: &str,
+        reference: &str,
+    ) -> Result<Option<RepositoryFile>, GitHubActionError> {
+        let Some(owner) = repository.clone().owner else {
+            return MissingOwnerSnafu.fail();
+        };
+        fetch_content(self, &owner.login, &repository.name, path, Some(reference)).await
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self))]
+    async fn search_issues(&self, query: &str) -> Result<Vec<Issue>, GitHubActionError> {
+        let page = self
+            .search()
+            .issues_and_pull_requests(query)
+            .per_page(100)
+            .send()
+            .await
+            .context(OctocrabSnafu)?;
+        self.all_pages(page).await.context(OctocrabSnafu)
+    }
+
+    #[allow(refining_impl_trait)]
+    #[instrument(skip(self))]
+    async fn list_installation_repositories(&self) -> Result<Vec<Repository>, GitHubActionError> {
+        #[derive(Deserialize)]
+        struct InstallationRepositories {
+            total_count: usize,
+            repositories: Vec<Repository>,
+        }
+        #[derive(Serialize)]
+        struct Pagination {
+            per_page: u8,
+            page: u32,
+        }
+
+        let mut repositories = Vec::new();
+        for page in 1.. {
+            let response: InstallationRepositories = self
+                .get(
+                    "/installation/repositories",
+                    Some(&Pagination {
+                        per_page: 100,
+                        page,
+                    }),
+                )
+                .await
+                .context(OctocrabSnafu)?;
+            let exhausted = response.repositories.is_empty();
+            repositories.extend(response.repositories);
+            if exhausted || repositories.len() >= response.total_count {
+                break;
+            }
+        }
+        Ok(repositories)
     }
 }
 
+/// A file at the reference, or else on the default branch.
+async fn fetch_content(
+    octocrab: &Octocrab,
+    owner: &str,
+    repository: &str,
+    path: &str,
+    reference: Option<&str>,
+) -> Result<Option<RepositoryFile>, GitHubActionError> {
+    let repos = octocrab.repos(owner, repository);
+    let mut request = repos.get_content().path(path);
+    if let Some(reference) = reference {
+        request = request.r#ref(reference);
+    }
+    match request.send().await {
+        Ok(mut content) => Ok(content.take_items().into_iter().next().and_then(|file| {
+            Some(RepositoryFile {
+                content: file.decoded_content()?,
+                sha: file.sha,
+            })
+        })),
+        Err(octocrab::Error::GitHub { source, .. })
+            if source.status_code == StatusCode::NOT_FOUND =>
+        {
+            Ok(None)
+        }
+        Err(err) => Err(err).context(OctocrabSnafu),
+    }
+}
+
+/// The decoded content of a file in a repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepositoryFile {
+    pub content: String,
+    /// The blob SHA, which changes with the content.
+    pub sha: String,
+}
+
+/// How GitHub answered a merge request which it understood.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeOutcome {
+    Merged,
+    /// Another merge landed in the meantime, trying again is likely to succeed.
+    BaseBranchModified,
+    /// New commits were pushed since the pull request was evaluated.
+    HeadBranchModified,
+    Rejected {
+        reason: String,
+    },
+}
+
 #[derive(Debug, Snafu)]
 pub enum GitHubActionError {
     #[snafu(display("Missing owner!"))]
@@ -78,4 +946,9 @@ pub enum GitHubActionError {
         source: octocrab::Error,
         backtrace: Backtrace,
     },
+    #[snafu(display("The GraphQL query failed: {message}"))]
+    GraphQl {
+        message: String,
+        backtrace: Backtrace,
+    },
 }
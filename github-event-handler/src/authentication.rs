@@ -31,6 +31,11 @@ pub trait InstallationAuthenticator: Clone + Send + Sync {
         &self,
         id: InstallationId,
     ) -> impl Future<Output = Result<impl GitHubApi, Self::Error>> + Send;
+
+    /// Every installation of the app.
+    fn list_installations(
+        &self,
+    ) -> impl Future<Output = Result<Vec<InstallationId>, Self::Error>> + Send;
 }
 
 #[derive(Debug, Snafu)]
@@ -62,4 +67,13 @@ impl InstallationAuthenticator for Octocrab {
     async fn for_installation(&self, id: InstallationId) -> Result<impl GitHubApi, Self::Error> {
         self.installation_and_token(id).await.map(|r| r.0)
     }
+
+    async fn list_installations(&self) -> Result<Vec<InstallationId>, Self::Error> {
+        let page = self.apps().installations().send().await?;
+        let installations = self.all_pages(page).await?;
+        Ok(installations
+            .into_iter()
+            .map(|installation| installation.id)
+            .collect())
+    }
 }
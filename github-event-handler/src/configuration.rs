@@ -0,0 +1,610 @@
+use crate::api::GitHubApi;
+use mergeable_compatibility_layer::configuration::compiled::CompiledConfiguration;
+use mergeable_compatibility_layer::configuration::version::ConfigurationError;
+use mergeable_compatibility_layer::configuration::Configuration;
+use octocrab::models::webhook_events::payload::PushWebhookEventPayload;
+use octocrab::models::{InstallationId, Repository};
+use snafu::{OptionExt, ResultExt, Snafu};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Where mergeable expects the configuration of a repository.
+pub const CONFIGURATION_PATH: &str = ".github/mergeable.yml";
+
+/// The repository of an organization holding the configuration shared by its repositories.
+pub const ORGANIZATION_REPOSITORY: &str = ".github";
+
+/// How long shared configurations are trusted before they are fetched again.
+const TIME_TO_LIVE: Duration = Duration::from_secs(5 * 60);
+
+/// How long the configuration on the default branch is trusted without a push event, in case
+/// one went missing or the cache doesn't receive them, like the one of the schedule.
+const VERIFY_AFTER: Duration = Duration::from_secs(60 * 60);
+
+/// How many configurations, and how much about each repository, are kept, the least recently
+/// used entry is evicted first.
+const CAPACITY: usize = 1024;
+
+/// Counts the lookups of configurations by their `result`, either `hit` or `miss`.
+const CACHE_LOOKUPS: &str = "configuration_cache_lookups_total";
+
+/// Push events only list the files of the first 20 commits.
+const LISTED_COMMITS: usize = 20;
+
+/// An installation and the full name of one of its repositories.
+type RepositoryKey = (InstallationId, String);
+
+/// A resolved configuration, or why its blob couldn't be parsed, which doesn't change until
+/// the blob does.
+type CachedConfiguration = Result<Option<Arc<CompiledConfiguration>>, Arc<ConfigurationError>>;
+
+/// Resolved configurations by repository and blob SHA, and the shared configurations they are
+/// based on.
+///
+/// A resolved configuration also depends on the configuration it extends, or the one of the
+/// organization it follows, it is stale once that changes as well.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigurationCache {
+    shared: Arc<Mutex<Lru<RepositoryKey, CachedSource>>>,
+    resolved: Arc<Mutex<ResolvedConfigurations>>,
+}
+
+#[derive(Debug)]
+struct CachedSource {
+    /// `None` if the repository has no configuration, which is worth remembering as well.
+    source: Option<String>,
+    fetched_at: Instant,
+}
+
+/// A map keeping the [`CAPACITY`] most recently used entries.
+#[derive(Debug)]
+struct Lru<K, V> {
+    entries: HashMap<K, (V, u64)>,
+    /// The keys by their last use, the first one was used least recently.
+    order: BTreeMap<u64, K>,
+    /// Increases with every use.
+    clock: u64,
+}
+
+impl<K, V> Default for Lru<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> Lru<K, V> {
+    fn get(&mut self, key: &K) -> Option<&mut V> {
+        let (value, used_at) = self.entries.get_mut(key)?;
+        self.clock += 1;
+        if let Some(key) = self.order.remove(used_at) {
+            self.order.insert(self.clock, key);
+        }
+        *used_at = self.clock;
+        Some(value)
+    }
+
+    /// Inserts the entry, returning the least recently used one if it had to be evicted.
+    fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        self.clock += 1;
+        if let Some((_, used_at)) = self.entries.insert(key.clone(), (value, self.clock)) {
+            self.order.remove(&used_at);
+        }
+        self.order.insert(self.clock, key);
+        if self.entries.len() <= CAPACITY {
+            return None;
+        }
+        let (_, key) = self.order.pop_first()?;
+        let (value, _) = self.entries.remove(&key)?;
+        Some((key, value))
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, used_at) = self.entries.remove(key)?;
+        self.order.remove(&used_at);
+        Some(value)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[derive(Debug, Default)]
+struct ResolvedConfigurations {
+    /// The blob SHA of the configuration on the default branch, `None` for repositories without
+    /// one, until a push changes it.
+    current: Lru<RepositoryKey, (Option<String>, Instant)>,
+    entries: Lru<(RepositoryKey, Option<String>), ResolvedEntry>,
+    /// The generation of a repository is the count of changes at its last change, configurations
+    /// resolved on top of an older generation are stale.
+    generations: Lru<RepositoryKey, u64>,
+    changes: u64,
+    /// The generation of repositories whose own one was evicted, the count of changes at the
+    /// last eviction, so configurations resolved on top of an evicted generation are stale.
+    evicted: u64,
+}
+
+#[derive(Debug)]
+struct ResolvedEntry {
+    configuration: CachedConfiguration,
+    /// The repository the configuration is based on and its generation at the time.
+    base: Option<(RepositoryKey, u64)>,
+}
+
+impl ResolvedConfigurations {
+    fn lookup(&mut self, key: &RepositoryKey, sha: &Option<String>) -> Option<CachedConfiguration> {
+        let entry_key = (key.clone(), sha.clone());
+        let entry = self.entries.get(&entry_key)?;
+        let base = entry.base.clone();
+        let configuration = entry.configuration.clone();
+        if let Some((base, generation)) = base {
+            if self.generation(&base) != generation {
+                self.entries.remove(&entry_key);
+                return None;
+            }
+        }
+        Some(configuration)
+    }
+
+    fn generation(&mut self, key: &RepositoryKey) -> u64 {
+        self.generations.get(key).copied().unwrap_or(self.evicted)
+    }
+
+    fn insert(&mut self, key: RepositoryKey, sha: Option<String>, resolved: Resolved) {
+        self.current
+            .insert(key.clone(), (sha.clone(), Instant::now()));
+        self.entries.insert(
+            (key, sha),
+            ResolvedEntry {
+                configuration: resolved.configuration,
+                base: resolved.base,
+            },
+        );
+    }
+
+    /// The base of a configuration with its current generation, to resolve the configuration on.
+    fn base_of(&mut self, key: RepositoryKey) -> (RepositoryKey, u64) {
+        let generation = self.generation(&key);
+        (key, generation)
+    }
+
+    /// Starts a new generation of the configurations based on the repository.
+    fn change(&mut self, key: RepositoryKey) {
+        self.current.remove(&key);
+        self.changes += 1;
+        if self.generations.insert(key, self.changes).is_some() {
+            self.evicted = self.changes;
+        }
+    }
+}
+
+/// A configuration as it was resolved, with the generation of its base before it was fetched.
+struct Resolved {
+    configuration: CachedConfiguration,
+    base: Option<(RepositoryKey, u64)>,
+}
+
+impl ConfigurationCache {
+    fn base(&self, key: RepositoryKey) -> (RepositoryKey, u64) {
+        let mut resolved = self.resolved.lock().expect("poisoned configuration cache");
+        resolved.base_of(key)
+    }
+
+    fn shared_source(&self, key: &RepositoryKey) -> Option<Option<String>> {
+        let mut shared = self.shared.lock().expect("poisoned configuration cache");
+        shared
+            .get(key)
+            .filter(|cached| cached.fetched_at.elapsed() < TIME_TO_LIVE)
+            .map(|cached| cached.source.clone())
+    }
+
+    fn insert_shared_source(&self, key: RepositoryKey, source: Option<String>) {
+        let mut shared = self.shared.lock().expect("poisoned configuration cache");
+        shared.insert(
+            key,
+            CachedSource {
+                source,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+
+    /// The configuration resolved for what is currently on the default branch.
+    fn current(&self, key: &RepositoryKey) -> Option<CachedConfiguration> {
+        let mut resolved = self.resolved.lock().expect("poisoned configuration cache");
+        let (sha, verified_at) = resolved.current.get(key)?.clone();
+        if verified_at.elapsed() >= VERIFY_AFTER {
+            return None;
+        }
+        resolved.lookup(key, &sha)
+    }
+
+    /// The configuration resolved before for the blob, which is now on the default branch.
+    fn resolved(&self, key: &RepositoryKey, sha: &Option<String>) -> Option<CachedConfiguration> {
+        let mut resolved = self.resolved.lock().expect("poisoned configuration cache");
+        let configuration = resolved.lookup(key, sha)?;
+        resolved
+            .current
+            .insert(key.clone(), (sha.clone(), Instant::now()));
+        Some(configuration)
+    }
+
+    fn insert(&self, key: RepositoryKey, sha: Option<String>, configuration: Resolved) {
+        let mut resolved = self.resolved.lock().expect("poisoned configuration cache");
+        resolved.insert(key, sha, configuration);
+    }
+
+    /// Forgets the configuration of the repository, which changed on its default branch.
+    ///
+    /// The configurations based on it, like the ones following the organization's shared
+    /// configuration or extending it, are resolved again when they are used next.
+    pub fn invalidate(&self, installation: InstallationId, owner: &str, repository: &str) {
+        let key = (installation, format!("{owner}/{repository}"));
+        let mut resolved = self.resolved.lock().expect("poisoned configuration cache");
+        resolved.change(key.clone());
+        drop(resolved);
+        let mut shared = self.shared.lock().expect("poisoned configuration cache");
+        shared.remove(&key);
+    }
+}
+
+/// Whether the push changed the configuration on the default branch of the repository.
+pub fn changes_configuration(push: &PushWebhookEventPayload, repository: &Repository) -> bool {
+    let default_branch = repository.default_branch.as_deref().unwrap_or("main");
+    if push.r#ref != format!("refs/heads/{default_branch}") {
+        return false;
+    }
+    // without the full list of files the configuration may have changed
+    if push.commits.len() >= LISTED_COMMITS || push.forced {
+        return true;
+    }
+    push.commits.iter().any(|commit| {
+        [&commit.added, &commit.removed, &commit.modified]
+            .into_iter()
+            .flatten()
+            .any(|path| path == CONFIGURATION_PATH)
+    })
+}
+
+/// The configuration the rules of the repository are evaluated with.
+///
+/// Repositories without a configuration follow the one of their organization's `.github`
+/// repository, configurations with `extends` are put on top of the referenced one.
+pub async fn resolve_configuration<A: GitHubApi>(
+    api: &A,
+    installation: InstallationId,
+    cache: &ConfigurationCache,
+    repository: &Repository,
+) -> Result<Option<Arc<CompiledConfiguration>>, LoadConfigurationError> {
+    let owner = &repository.owner.as_ref().context(MissingOwnerSnafu)?.login;
+    let key = (installation, format!("{owner}/{}", repository.name));
+    if let Some(configuration) = cache.current(&key) {
+        metrics::counter!(CACHE_LOOKUPS, "result" => "hit").increment(1);
+        return configuration.context(ParseSnafu);
+    }
+    let file = api
+        .fetch_file(repository, CONFIGURATION_PATH)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(FetchSnafu)?;
+    let sha = file.as_ref().map(|file| file.sha.clone());
+    if let Some(configuration) = cache.resolved(&key, &sha) {
+        metrics::counter!(CACHE_LOOKUPS, "result" => "hit").increment(1);
+        return configuration.context(ParseSnafu);
+    }
+    metrics::counter!(CACHE_LOOKUPS, "result" => "miss").increment(1);
+    let (configuration, base) = match file {
+        None if repository.name == ORGANIZATION_REPOSITORY => (Ok(None), None),
+        None => {
+            let base = cache.base((installation, format!("{owner}/{ORGANIZATION_REPOSITORY}")));
+            let configuration =
+                shared_configuration(api, installation, cache, owner, ORGANIZATION_REPOSITORY)
+                    .await;
+            (configuration, Some(base))
+        }
+        Some(file) => match Configuration::from_yaml(&file.content) {
+            Err(err) => (
+                Err(LoadConfigurationError::Parse { source: err.into() }),
+                None,
+            ),
+            Ok(configuration) => {
+                let base = configuration.extends().map(|extends| {
+                    let (base_owner, base_repository) = base_repository(owner, extends);
+                    cache.base((installation, format!("{base_owner}/{base_repository}")))
+                });
+                let configuration = extend(api, installation, cache, owner, configuration).await;
+                (configuration.map(Some), base)
+            }
+        },
+    };
+    let configuration = match configuration {
+        Ok(configuration) => Ok(configuration.map(|configuration| {
+            // e.g. validators listening to events they can't handle, which would give wrong
+            // results
+            for warning in configuration.lint() {
+                tracing::warn!(%warning, repo = %repository.name, "configuration problem");
+            }
+            Arc::new(configuration.compile())
+        })),
+        // a blob which can't be parsed isn't fetched and parsed again for every event
+        Err(LoadConfigurationError::Parse { source }) => Err(source),
+        Err(err) => return Err(err),
+    };
+    let resolved = Resolved {
+        configuration: configuration.clone(),
+        base,
+    };
+    cache.insert(key, sha, resolved);
+    configuration.context(ParseSnafu)
+}
+
+/// The owner and name of the repository `extends` refers to, the owner defaults to `owner`.
+fn base_repository<'a>(owner: &'a str, extends: &'a str) -> (&'a str, &'a str) {
+    extends.split_once('/').unwrap_or((owner, extends))
+}
+
+async fn extend<A: GitHubApi>(
+    api: &A,
+    installation: InstallationId,
+    cache: &ConfigurationCache,
+    owner: &str,
+    configuration: Configuration,
+) -> Result<Configuration, LoadConfigurationError> {
+    let Some(extends) = configuration.extends() else {
+        return Ok(configuration);
+    };
+    let (base_owner, base_repository) = base_repository(owner, extends);
+    let base = shared_configuration(api, installation, cache, base_owner, base_repository)
+        .await?
+        .context(MissingBaseSnafu { extends })?;
+    if base.extends().is_some() {
+        tracing::warn!(
+            extends,
+            "ignoring the extends of the extended configuration"
+        );
+    }
+    Ok(configuration.extend(base))
+}
+
+async fn shared_configuration<A: GitHubApi>(
+    api: &A,
+    installation: InstallationId,
+    cache: &ConfigurationCache,
+    owner: &str,
+    repository: &str,
+) -> Result<Option<Configuration>, LoadConfigurationError> {
+    let key = (installation, format!("{owner}/{repository}"));
+    let source = match cache.shared_source(&key) {
+        Some(source) => source,
+        None => {
+            let source = api
+                .fetch_repository_file(owner, repository, CONFIGURATION_PATH)
+                .await
+                .map_err(|err| Box::new(err) as _)
+                .context(FetchSnafu)?
+                .map(|file| file.content);
+            cache.insert_shared_source(key, source.clone());
+            source
+        }
+    };
+    source
+        .map(|source| {
+            Configuration::from_yaml(&source)
+                .map_err(Arc::new)
+                .context(ParseSnafu)
+        })
+        .transpose()
+}
+
+#[derive(Debug, Snafu)]
+pub enum LoadConfigurationError {
+    #[snafu(display("Unable to fetch the configuration: {source}"))]
+    Fetch {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("{source}"))]
+    Parse { source: Arc<ConfigurationError> },
+    #[snafu(display("Missing owner of the repository"))]
+    MissingOwner,
+    #[snafu(display("The extended configuration of '{extends}' does not exist"))]
+    MissingBase { extends: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::{
+        changes_configuration, resolve_configuration, ConfigurationCache, Resolved,
+        ResolvedConfigurations, CAPACITY, CONFIGURATION_PATH,
+    };
+    use crate::fake::{repository, FakeApi};
+    use octocrab::models::webhook_events::payload::PushWebhookEventPayload;
+    use octocrab::models::InstallationId;
+
+    const INSTALLATION: InstallationId = InstallationId(1);
+
+    fn rule(name: &str) -> String {
+        format!("version: 2\nmergeable:\n  - when: pull_request.*\n    name: {name}\n")
+    }
+
+    async fn rule_names(api: &FakeApi, cache: &ConfigurationCache, name: &str) -> Vec<String> {
+        let configuration = resolve_configuration(api, INSTALLATION, cache, &repository(name))
+            .await
+            .unwrap()
+            .unwrap();
+        configuration
+            .rules_for("pull_request.opened")
+            .filter_map(|rule| rule.rule().name().map(str::to_owned))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn resolved_configurations_are_invalidated_with_their_base() {
+        let extending = format!("extends: base\n{}", rule("own"));
+        let api = FakeApi::default()
+            .with_file("octo/repo", CONFIGURATION_PATH, &extending, "1")
+            .with_file("octo/base", CONFIGURATION_PATH, &rule("base"), "2")
+            .with_file("octo/.github", CONFIGURATION_PATH, &rule("shared"), "3");
+        let cache = ConfigurationCache::default();
+
+        assert_eq!(rule_names(&api, &cache, "repo").await, ["base", "own"]);
+        assert_eq!(
+            api.calls(),
+            [
+                "fetch octo/repo/.github/mergeable.yml",
+                "fetch octo/base/.github/mergeable.yml",
+            ]
+        );
+        assert_eq!(rule_names(&api, &cache, "repo").await, ["base", "own"]);
+        assert_eq!(rule_names(&api, &cache, "other").await, ["shared"]);
+        assert_eq!(
+            api.calls(),
+            [
+                "fetch octo/other/.github/mergeable.yml",
+                "fetch octo/.github/.github/mergeable.yml",
+            ]
+        );
+
+        api.put_file("octo/base", CONFIGURATION_PATH, &rule("changed"), "4");
+        cache.invalidate(INSTALLATION, "octo", "base");
+        assert_eq!(rule_names(&api, &cache, "repo").await, ["changed", "own"]);
+        assert_eq!(rule_names(&api, &cache, "other").await, ["shared"]);
+        assert_eq!(
+            api.calls(),
+            [
+                "fetch octo/repo/.github/mergeable.yml",
+                "fetch octo/base/.github/mergeable.yml",
+            ]
+        );
+
+        api.put_file(
+            "octo/.github",
+            CONFIGURATION_PATH,
+            &rule("organization"),
+            "5",
+        );
+        cache.invalidate(INSTALLATION, "octo", ".github");
+        assert_eq!(rule_names(&api, &cache, "repo").await, ["changed", "own"]);
+        assert_eq!(rule_names(&api, &cache, "other").await, ["organization"]);
+        assert_eq!(
+            api.calls(),
+            [
+                "fetch octo/other/.github/mergeable.yml",
+                "fetch octo/.github/.github/mergeable.yml",
+            ]
+        );
+    }
+
+    #[test]
+    fn the_least_recently_used_configuration_is_evicted() {
+        let mut resolved = ResolvedConfigurations::default();
+        let key = |repository: usize| (INSTALLATION, format!("octo/{repository}"));
+        let unresolved = || Resolved {
+            configuration: Ok(None),
+            base: None,
+        };
+        for repository in 0..CAPACITY {
+            resolved.insert(key(repository), None, unresolved());
+        }
+        assert!(resolved.lookup(&key(0), &None).is_some());
+
+        resolved.insert(key(CAPACITY), None, unresolved());
+
+        assert_eq!(resolved.entries.len(), CAPACITY);
+        assert_eq!(resolved.current.len(), CAPACITY);
+        assert!(resolved.lookup(&key(0), &None).is_some());
+        assert!(resolved.lookup(&key(1), &None).is_none());
+        assert!(resolved.lookup(&key(CAPACITY), &None).is_some());
+
+        // evicting the generation of a base makes what is based on it stale, not current again
+        let based = Resolved {
+            configuration: Ok(None),
+            base: Some(resolved.base_of(key(0))),
+        };
+        resolved.insert(key(0), None, based);
+        resolved.change(key(0));
+        for repository in 1..=CAPACITY {
+            resolved.change(key(repository));
+        }
+        assert_eq!(resolved.generations.len(), CAPACITY);
+        assert!(resolved.lookup(&key(0), &None).is_none());
+    }
+
+    #[tokio::test]
+    async fn configurations_which_cant_be_parsed_are_cached_by_their_blob() {
+        let api = FakeApi::default().with_file("octo/repo", CONFIGURATION_PATH, "version: [", "1");
+        let cache = ConfigurationCache::default();
+
+        for _ in 0..2 {
+            let err = resolve_configuration(&api, INSTALLATION, &cache, &repository("repo"))
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().starts_with("Invalid configuration"),
+                "{err}"
+            );
+        }
+        assert_eq!(api.calls(), ["fetch octo/repo/.github/mergeable.yml"]);
+
+        api.put_file("octo/repo", CONFIGURATION_PATH, &rule("fixed"), "2");
+        cache.invalidate(INSTALLATION, "octo", "repo");
+        assert_eq!(rule_names(&api, &cache, "repo").await, ["fixed"]);
+    }
+
+    fn push(r#ref: &str, modified: &[&str], forced: bool) -> PushWebhookEventPayload {
+        let user = serde_json::json!({"name": "octocat", "email": "octocat@github.com"});
+        let commit = serde_json::json!({
+            "id": "abc",
+            "tree_id": "def",
+            "distinct": true,
+            "message": "Update",
+            "timestamp": "2024-01-01T00:00:00Z",
+            "url": "https://github.com/octo/repo/commit/abc",
+            "author": user,
+            "committer": user,
+            "modified": modified,
+        });
+        serde_json::from_value(serde_json::json!({
+            "after": "abc",
+            "before": "000",
+            "commits": [commit],
+            "compare": "https://github.com/octo/repo/compare/000...abc",
+            "created": false,
+            "deleted": false,
+            "forced": forced,
+            "pusher": user,
+            "ref": r#ref,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn only_pushes_to_the_configuration_on_the_default_branch_change_it() {
+        let repository = repository("repo");
+
+        let configuration = [CONFIGURATION_PATH];
+        assert!(changes_configuration(
+            &push("refs/heads/main", &configuration, false),
+            &repository
+        ));
+        assert!(!changes_configuration(
+            &push("refs/heads/feature", &configuration, false),
+            &repository
+        ));
+        assert!(!changes_configuration(
+            &push("refs/heads/main", &["README.md"], false),
+            &repository
+        ));
+        // a force push may have removed a commit changing it
+        assert!(changes_configuration(
+            &push("refs/heads/main", &["README.md"], true),
+            &repository
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::api::GitHubApi;
+use crate::membership::MembershipCache;
+use chrono::{DateTime, Utc};
+use mergeable_compatibility_layer::evaluation::{
+    ChangedFile, Clock, EvaluationContext, FileStatus, Item, SystemClock, Visibility,
+};
+use octocrab::models::issues::Issue;
+use octocrab::models::pulls::PullRequest;
+use octocrab::models::repos::{DiffEntry, DiffEntryStatus};
+use octocrab::models::{InstallationId, Repository};
+use snafu::{OptionExt, ResultExt, Snafu};
+
+/// Evaluates rules against an event of a repository by querying the GitHub API.
+pub struct EventContext<'a, A> {
+    api: &'a A,
+    clock: &'a dyn Clock,
+    installation: InstallationId,
+    memberships: &'a MembershipCache,
+    repository: &'a Repository,
+    pull_request: Option<&'a PullRequest>,
+    payload: &'a serde_json::Value,
+}
+
+impl<'a, A: GitHubApi> EventContext<'a, A> {
+    pub fn new(
+        api: &'a A,
+        installation: InstallationId,
+        memberships: &'a MembershipCache,
+        repository: &'a Repository,
+        payload: &'a serde_json::Value,
+    ) -> Self {
+        Self {
+            api,
+            clock: &SystemClock,
+            installation,
+            memberships,
+            repository,
+            pull_request: None,
+            payload,
+        }
+    }
+
+    pub fn with_clock(self, clock: &'a dyn Clock) -> Self {
+        Self { clock, ..self }
+    }
+
+    /// Scopes the context to the pull request the event is about.
+    pub fn with_pull_request(self, pull_request: &'a PullRequest) -> Self {
+        Self {
+            pull_request: Some(pull_request),
+            ..self
+        }
+    }
+}
+
+impl<A: GitHubApi> EvaluationContext for EventContext<'_, A> {
+    type Error = ContextError;
+
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    fn author(&self) -> &str {
+        match self.pull_request {
+            Some(pull_request) => pull_request
+                .user
+                .as_ref()
+                .map(|user| user.login.as_str())
+                .unwrap_or_default(),
+            None => self
+                .payload
+                .pointer("/issue/user/login")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn payload(&self) -> &serde_json::Value {
+        self.payload
+    }
+
+    fn repository_name(&self) -> &str {
+        &self.repository.name
+    }
+
+    fn repository_visibility(&self) -> Visibility {
+        match self.repository.visibility.as_deref() {
+            Some("public") => Visibility::Public,
+            Some("internal") => Visibility::Internal,
+            Some(_) => Visibility::Private,
+            // older payloads only carry the private flag
+            None if self.repository.private == Some(false) => Visibility::Public,
+            None => Visibility::Private,
+        }
+    }
+
+    async fn repository_topics(&self) -> Result<Vec<String>, Self::Error> {
+        if let Some(topics) = &self.repository.topics {
+            return Ok(topics.clone());
+        }
+        self.api
+            .list_repository_topics(self.repository)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)
+    }
+
+    async fn changed_files(&self) -> Result<Vec<ChangedFile>, Self::Error> {
+        let Some(pull_request) = self.pull_request else {
+            return Ok(Vec::new());
+        };
+        let files = self
+            .api
+            .list_pull_request_files(self.repository, pull_request.number)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+        Ok(files.into_iter().map(changed_file).collect())
+    }
+
+    async fn projects(&self) -> Result<Vec<String>, Self::Error> {
+        let number = match self.pull_request {
+            Some(pull_request) => Some(pull_request.number),
+            None => self
+                .payload
+                .get("issue")
+                .and_then(|issue| issue.get("number"))
+                .and_then(serde_json::Value::as_u64),
+        };
+        let Some(number) = number else {
+            return Ok(Vec::new());
+        };
+        self.api
+            .list_item_projects(self.repository, number)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)
+    }
+
+    async fn search_items(&self, qualifiers: &str) -> Result<Vec<Item>, Self::Error> {
+        let full_name = match &self.repository.full_name {
+            Some(full_name) => full_name.clone(),
+            None => {
+                let owner = self.repository.owner.as_ref().context(MissingOwnerSnafu)?;
+                format!("{}/{}", owner.login, self.repository.name)
+            }
+        };
+        let issues = self
+            .api
+            .search_issues(&format!("repo:{full_name} {qualifiers}"))
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+        Ok(issues.into_iter().map(item).collect())
+    }
+
+    async fn team_members(&self, team: &str) -> Result<Vec<String>, Self::Error> {
+        if let Some(members) = self.memberships.get(self.installation, team) {
+            return Ok(members);
+        }
+        // teams without an organization belong to the owner of the repository
+        let (org, team_slug) = match team.split_once('/') {
+            Some((org, team_slug)) => (org, team_slug),
+            None => {
+                let owner = self.repository.owner.as_ref().context(MissingOwnerSnafu)?;
+                (owner.login.as_str(), team)
+            }
+        };
+        let members = self
+            .api
+            .list_team_members(org, team_slug)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ApiSnafu)?;
+        self.memberships
+            .insert(self.installation, team, members.clone());
+        Ok(members)
+    }
+}
+
+fn changed_file(entry: DiffEntry) -> ChangedFile {
+    let status = match entry.status {
+        DiffEntryStatus::Added => FileStatus::Added,
+        DiffEntryStatus::Removed => FileStatus::Removed,
+        DiffEntryStatus::Modified => FileStatus::Modified,
+        DiffEntryStatus::Renamed => FileStatus::Renamed,
+        DiffEntryStatus::Copied => FileStatus::Copied,
+        DiffEntryStatus::Unchanged => FileStatus::Unchanged,
+        _ => FileStatus::Changed,
+    };
+    ChangedFile {
+        filename: entry.filename,
+        status,
+    }
+}
+
+fn item(issue: Issue) -> Item {
+    Item {
+        number: issue.number,
+        title: issue.title,
+        pull_request: issue.pull_request.is_some(),
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ContextError {
+    #[snafu(display("Unable to query the GitHub API: {source}"))]
+    Api {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Missing owner of the repository"))]
+    MissingOwner,
+}
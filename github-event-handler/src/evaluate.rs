@@ -0,0 +1,207 @@
+use crate::action::plan::{execute, Target};
+use crate::action::ActionError;
+use crate::api::GitHubApi;
+use crate::configuration::{resolve_configuration, ConfigurationCache, LoadConfigurationError};
+use crate::context::EventContext;
+use crate::membership::MembershipCache;
+use mergeable_compatibility_layer::action::Plan;
+use mergeable_compatibility_layer::evaluation::template::EventVars;
+use mergeable_compatibility_layer::evaluation::RuleResult;
+use octocrab::models::{InstallationId, IssueState, Repository};
+use snafu::{ResultExt, Snafu};
+use tracing::instrument;
+
+/// The event the rules are evaluated for.
+#[derive(Debug, Clone, Copy)]
+pub struct Event<'a> {
+    /// As rules listen to it, e.g. `pull_request.opened`.
+    pub name: &'a str,
+    pub payload: &'a serde_json::Value,
+    pub target: Target<'a>,
+}
+
+/// An event about a commit rather than a pull request or issue.
+#[derive(Debug, Clone, Copy)]
+pub struct CommitEvent<'a> {
+    /// As rules listen to it, e.g. `status.success`.
+    pub name: &'a str,
+    pub sha: &'a str,
+    pub payload: &'a serde_json::Value,
+}
+
+/// Evaluates the rules of the repository listening to the event and executes their actions on
+/// the pull request or issue the event is about.
+#[instrument(skip(api, memberships, configurations, repository, event), fields(repo = %repository.name, event = event.name))]
+pub async fn evaluate_event<A: GitHubApi>(
+    api: &A,
+    installation: InstallationId,
+    memberships: &MembershipCache,
+    configurations: &ConfigurationCache,
+    repository: &Repository,
+    event: Event<'_>,
+) -> Result<Vec<RuleResult>, EvaluateEventError> {
+    let Some(configuration) = resolve_configuration(api, installation, configurations, repository)
+        .await
+        .context(ConfigurationSnafu)?
+    else {
+        return Ok(Vec::new());
+    };
+    let mut context = EventContext::new(api, installation, memberships, repository, event.payload);
+    if let Some(pull_request) = event.target.pull_request {
+        context = context.with_pull_request(pull_request);
+    }
+    let mut plan = Plan::default();
+    let mut results = Vec::new();
+    for compiled in configuration.rules_for(event.name) {
+        let result = compiled.evaluate(&context).await;
+        let rule = compiled.rule();
+        let key = configuration.rule_key(rule);
+        if let Err(err) = plan.add(&key, rule, &result, EventVars::new(&context)) {
+            tracing::warn!(%err, rule = key, "unable to plan the actions of the rule");
+        }
+        tracing::info!(
+            rule = key,
+            status = result.status.as_str(),
+            "evaluated rule"
+        );
+        results.push(result);
+    }
+    execute(api, &context, repository, event.target, &plan)
+        .await
+        .context(ActionSnafu)?;
+    Ok(results)
+}
+
+/// Evaluates the rules listening to an event about a commit, like a status or a check suite,
+/// for every open pull request whose head is the commit.
+///
+/// The pull request is added to the payload, as it is for the events of pull requests.
+#[instrument(skip(api, memberships, configurations, repository, event), fields(repo = %repository.name, event = event.name, sha = event.sha))]
+pub async fn evaluate_commit<A: GitHubApi>(
+    api: &A,
+    installation: InstallationId,
+    memberships: &MembershipCache,
+    configurations: &ConfigurationCache,
+    repository: &Repository,
+    event: CommitEvent<'_>,
+) -> Result<Vec<RuleResult>, EvaluateEventError> {
+    let CommitEvent { name, sha, payload } = event;
+    let pull_requests = api
+        .list_commit_pull_requests(repository, sha)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(PullRequestsSnafu)?;
+    let mut results = Vec::new();
+    for pull_request in &pull_requests {
+        if pull_request.head.sha != sha || pull_request.state != Some(IssueState::Open) {
+            continue;
+        }
+        let mut payload = payload.clone();
+        if let Some(payload) = payload.as_object_mut() {
+            payload.insert(
+                "pull_request".to_owned(),
+                serde_json::to_value(pull_request).unwrap_or_default(),
+            );
+        }
+        let target = Target {
+            number: pull_request.number,
+            pull_request: Some(pull_request),
+        };
+        let event = Event {
+            name,
+            payload: &payload,
+            target,
+        };
+        results.extend(
+            evaluate_event(
+                api,
+                installation,
+                memberships,
+                configurations,
+                repository,
+                event,
+            )
+            .await?,
+        );
+    }
+    Ok(results)
+}
+
+#[derive(Debug, Snafu)]
+pub enum EvaluateEventError {
+    #[snafu(display("Unable to list the pull requests of the commit: {source}"))]
+    PullRequests {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Unable to load the configuration: {source}"))]
+    Configuration { source: LoadConfigurationError },
+    #[snafu(display("Unable to execute the actions: {source}"))]
+    Action { source: ActionError },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::{ConfigurationCache, CONFIGURATION_PATH};
+    use crate::evaluate::{evaluate_commit, CommitEvent};
+    use crate::fake::{pull_request, repository, FakeApi};
+    use crate::membership::MembershipCache;
+    use mergeable_compatibility_layer::evaluation::RuleStatus;
+    use octocrab::models::InstallationId;
+
+    #[tokio::test]
+    async fn statuses_are_evaluated_for_the_pull_requests_of_the_commit() {
+        let api = FakeApi {
+            pull_requests: vec![
+                pull_request(1, "abc", Some(true)),
+                pull_request(2, "def", Some(true)),
+            ],
+            ..Default::default()
+        }
+        .with_file(
+            "octo/repo",
+            CONFIGURATION_PATH,
+            indoc::indoc! {"
+                version: 2
+                mergeable:
+                  - when: status.*, check_suite.completed
+                    name: green
+                    validate:
+                      - do: author
+                        must_exclude:
+                          regex: 'bot$'
+                    pass:
+                      - do: merge
+            "},
+            "1",
+        );
+        let payload = serde_json::json!({ "state": "success" });
+
+        let results = evaluate_commit(
+            &api,
+            InstallationId(1),
+            &MembershipCache::default(),
+            &ConfigurationCache::default(),
+            &repository("repo"),
+            CommitEvent {
+                name: "status.success",
+                sha: "abc",
+                payload: &payload,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].status, RuleStatus::Pass);
+        // the pull request whose head is another commit is left alone
+        assert_eq!(
+            api.calls(),
+            [
+                "pull requests of abc".to_owned(),
+                format!("fetch octo/repo/{CONFIGURATION_PATH}"),
+                "get #1".to_owned(),
+                "merge #1".to_owned(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,432 @@
+//! A GitHub API answering from fixtures and recording the changes it is asked to make.
+
+use crate::api::{GitHubApi, MergeOutcome, RepositoryFile};
+use mergeable_compatibility_layer::action::{CheckRun, MergeCommit, StateReason};
+use octocrab::models::issues::{Comment, Issue};
+use octocrab::models::pulls::PullRequest;
+use octocrab::models::repos::DiffEntry;
+use octocrab::models::{CheckRunId, CommentId, Repository};
+use snafu::{OptionExt, Snafu};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Clones share the files and the calls.
+#[derive(Debug, Default, Clone)]
+pub struct FakeApi {
+    /// Files by the full name of their repository and their path, as `path@reference` for the
+    /// files at a commit.
+    pub files: Arc<Mutex<HashMap<(String, String), RepositoryFile>>>,
+    /// The files changed by the pull requests.
+    pub pull_request_files: Vec<String>,
+    /// The pull requests of every commit.
+    pub pull_requests: Vec<PullRequest>,
+    /// The items every search finds.
+    pub search_results: Vec<u64>,
+    pub comments: Vec<Comment>,
+    pub repository_labels: Vec<String>,
+    /// The queries, fetches and changes, in the order they were made.
+    pub calls: Arc<Mutex<Vec<String>>>,
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("{what} is not part of the fixtures"))]
+pub struct FakeError {
+    what: String,
+}
+
+impl FakeApi {
+    pub fn with_file(self, repository: &str, path: &str, content: &str, sha: &str) -> Self {
+        self.put_file(repository, path, content, sha);
+        self
+    }
+
+    pub fn put_file(&self, repository: &str, path: &str, content: &str, sha: &str) {
+        let file = RepositoryFile {
+            content: content.to_owned(),
+            sha: sha.to_owned(),
+        };
+        let mut files = self.files.lock().unwrap();
+        files.insert((repository.to_owned(), path.to_owned()), file);
+    }
+
+    /// Takes the calls made so far.
+    pub fn calls(&self) -> Vec<String> {
+        std::mem::take(&mut self.calls.lock().unwrap())
+    }
+
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn file(&self, repository: &str, path: &str) -> Option<RepositoryFile> {
+        self.record(format!("fetch {repository}/{path}"));
+        let files = self.files.lock().unwrap();
+        files
+            .get(&(repository.to_owned(), path.to_owned()))
+            .cloned()
+    }
+}
+
+/// The login of the app in the fixtures.
+pub const BOT_LOGIN: &str = "mergeable[bot]";
+
+/// A repository of the `octo` organization.
+pub fn repository(name: &str) -> Repository {
+    serde_json::from_value(serde_json::json!({
+        "id": 1,
+        "name": name,
+        "full_name": format!("octo/{name}"),
+        "url": format!("https://api.github.com/repos/octo/{name}"),
+        "default_branch": "main",
+        "owner": user("octo", "Organization"),
+    }))
+    .unwrap()
+}
+
+pub fn user(login: &str, r#type: &str) -> serde_json::Value {
+    let url = format!("https://api.github.com/users/{login}");
+    serde_json::json!({
+        "login": login,
+        "id": 1,
+        "node_id": "MDQ6VXNlcjE=",
+        "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+        "gravatar_id": "",
+        "url": url,
+        "html_url": url,
+        "followers_url": url,
+        "following_url": url,
+        "gists_url": url,
+        "starred_url": url,
+        "subscriptions_url": url,
+        "organizations_url": url,
+        "repos_url": url,
+        "events_url": url,
+        "received_events_url": url,
+        "type": r#type,
+        "site_admin": false,
+    })
+}
+
+/// An open pull request at the head `sha`, `mergeable` as GitHub computed it, if it did.
+pub fn pull_request(number: u64, sha: &str, mergeable: Option<bool>) -> PullRequest {
+    let url = format!("https://api.github.com/repos/octo/repo/pulls/{number}");
+    let branch = |name: &str, sha: &str| {
+        serde_json::json!({
+            "label": format!("octo:{name}"),
+            "ref": name,
+            "sha": sha,
+            "user": user("octo", "Organization"),
+        })
+    };
+    serde_json::from_value(serde_json::json!({
+        "url": url,
+        "id": number,
+        "number": number,
+        "state": "open",
+        "title": format!("Pull request {number}"),
+        "user": user("author", "User"),
+        "head": branch("feature", sha),
+        "base": branch("main", "0000000000000000000000000000000000000000"),
+        "mergeable": mergeable,
+        "mergeable_state": if mergeable.is_some() { "clean" } else { "unknown" },
+    }))
+    .unwrap()
+}
+
+/// A comment on pull request or issue #1.
+pub fn comment(id: u64, login: &str, body: &str) -> Comment {
+    let url = format!("https://api.github.com/repos/octo/repo/issues/comments/{id}");
+    let r#type = if login.ends_with("[bot]") {
+        "Bot"
+    } else {
+        "User"
+    };
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "node_id": "MDEyOklzc3VlQ29tbWVudDE=",
+        "url": url,
+        "html_url": url,
+        "issue_url": "https://api.github.com/repos/octo/repo/issues/1",
+        "body": body,
+        "user": user(login, r#type),
+        "author_association": "NONE",
+        "created_at": "2024-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+pub fn issue(number: u64) -> Issue {
+    let url = format!("https://api.github.com/repos/octo/repo/issues/{number}");
+    serde_json::from_value(serde_json::json!({
+        "id": number,
+        "node_id": "MDU6SXNzdWUx",
+        "url": url,
+        "repository_url": "https://api.github.com/repos/octo/repo",
+        "labels_url": url,
+        "comments_url": url,
+        "events_url": url,
+        "html_url": url,
+        "number": number,
+        "state": "open",
+        "title": format!("Issue {number}"),
+        "user": user("author", "User"),
+        "labels": [],
+        "assignees": [],
+        "author_association": "MEMBER",
+        "locked": false,
+        "comments": 0,
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+impl GitHubApi for FakeApi {
+    #[allow(refining_impl_trait)]
+    async fn create_check_run(
+        &self,
+        _: &Repository,
+        head_sha: &str,
+        name: &str,
+        check_run: &CheckRun,
+    ) -> Result<CheckRunId, FakeError> {
+        self.record(format!("check run {name} on {head_sha}: {check_run:?}"));
+        Ok(CheckRunId(1))
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_pull_request_files(
+        &self,
+        _: &Repository,
+        number: u64,
+    ) -> Result<Vec<DiffEntry>, FakeError> {
+        self.record(format!("files #{number}"));
+        let entries = self.pull_request_files.iter().map(|filename| {
+            serde_json::json!({
+                "sha": "bbcd538c8e72b8c175046e27cc8f907076331401",
+                "filename": filename,
+                "status": "modified",
+                "additions": 1,
+                "deletions": 1,
+                "changes": 2,
+                "blob_url": "https://github.com/octo/repo/blob",
+                "raw_url": "https://github.com/octo/repo/raw",
+                "contents_url": "https://api.github.com/repos/octo/repo/contents",
+            })
+        });
+        Ok(entries
+            .map(|entry| serde_json::from_value(entry).unwrap())
+            .collect())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_repository_topics(&self, _: &Repository) -> Result<Vec<String>, FakeError> {
+        Ok(Vec::new())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_team_members(&self, _: &str, _: &str) -> Result<Vec<String>, FakeError> {
+        Ok(Vec::new())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_item_projects(&self, _: &Repository, _: u64) -> Result<Vec<String>, FakeError> {
+        Ok(Vec::new())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn get_pull_request(
+        &self,
+        _: &Repository,
+        number: u64,
+    ) -> Result<PullRequest, FakeError> {
+        self.record(format!("get #{number}"));
+        let pull_request = self
+            .pull_requests
+            .iter()
+            .find(|pull_request| pull_request.number == number);
+        pull_request.cloned().context(FakeSnafu {
+            what: format!("pull request #{number}"),
+        })
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_commit_pull_requests(
+        &self,
+        _: &Repository,
+        sha: &str,
+    ) -> Result<Vec<PullRequest>, FakeError> {
+        self.record(format!("pull requests of {sha}"));
+        Ok(self.pull_requests.clone())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn merge_pull_request(
+        &self,
+        _: &Repository,
+        number: u64,
+        _: &str,
+        _: &MergeCommit,
+    ) -> Result<MergeOutcome, FakeError> {
+        self.record(format!("merge #{number}"));
+        Ok(MergeOutcome::Merged)
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn bot_login(&self) -> Result<String, FakeError> {
+        Ok(BOT_LOGIN.to_owned())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_issue_comments(&self, _: &Repository, _: u64) -> Result<Vec<Comment>, FakeError> {
+        Ok(self.comments.clone())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn create_issue_comment(
+        &self,
+        _: &Repository,
+        number: u64,
+        body: &str,
+    ) -> Result<(), FakeError> {
+        self.record(format!("comment #{number}: {body}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn update_issue_comment(
+        &self,
+        _: &Repository,
+        id: CommentId,
+        body: &str,
+    ) -> Result<(), FakeError> {
+        self.record(format!("update comment {id}: {body}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn delete_issue_comment(&self, _: &Repository, id: CommentId) -> Result<(), FakeError> {
+        self.record(format!("delete comment {id}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn get_issue(&self, _: &Repository, number: u64) -> Result<Issue, FakeError> {
+        Ok(issue(number))
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn close_issue(
+        &self,
+        _: &Repository,
+        number: u64,
+        _: StateReason,
+    ) -> Result<(), FakeError> {
+        self.record(format!("close #{number}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn request_reviewers(
+        &self,
+        _: &Repository,
+        number: u64,
+        reviewers: &[String],
+        team_reviewers: &[String],
+    ) -> Result<(), FakeError> {
+        self.record(format!(
+            "request reviews #{number}: {reviewers:?} {team_reviewers:?}"
+        ));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn add_assignees(
+        &self,
+        _: &Repository,
+        number: u64,
+        assignees: &[String],
+    ) -> Result<(), FakeError> {
+        self.record(format!("assign #{number}: {assignees:?}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_issue_labels(&self, _: &Repository, _: u64) -> Result<Vec<String>, FakeError> {
+        Ok(Vec::new())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_repository_labels(&self, _: &Repository) -> Result<Vec<String>, FakeError> {
+        Ok(self.repository_labels.clone())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn create_label(&self, _: &Repository, name: &str, _: &str) -> Result<(), FakeError> {
+        self.record(format!("create label {name}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn add_issue_labels(
+        &self,
+        _: &Repository,
+        number: u64,
+        labels: &[String],
+    ) -> Result<(), FakeError> {
+        self.record(format!("label #{number}: {labels:?}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn remove_issue_label(
+        &self,
+        _: &Repository,
+        number: u64,
+        label: &str,
+    ) -> Result<(), FakeError> {
+        self.record(format!("unlabel #{number}: {label}"));
+        Ok(())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn fetch_file(
+        &self,
+        repository: &Repository,
+        path: &str,
+    ) -> Result<Option<RepositoryFile>, FakeError> {
+        Ok(self.file(&format!("octo/{}", repository.name), path))
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn fetch_repository_file(
+        &self,
+        owner: &str,
+        repository: &str,
+        path: &str,
+    ) -> Result<Option<RepositoryFile>, FakeError> {
+        Ok(self.file(&format!("{owner}/{repository}"), path))
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn fetch_file_at(
+        &self,
+        repository: &Repository,
+        path: &str,
+        reference: &str,
+    ) -> Result<Option<RepositoryFile>, FakeError> {
+        let path = format!("{path}@{reference}");
+        Ok(self.file(&format!("octo/{}", repository.name), &path))
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn search_issues(&self, query: &str) -> Result<Vec<Issue>, FakeError> {
+        self.record(format!("search {query}"));
+        Ok(self.search_results.iter().copied().map(issue).collect())
+    }
+
+    #[allow(refining_impl_trait)]
+    async fn list_installation_repositories(&self) -> Result<Vec<Repository>, FakeError> {
+        Ok(Vec::new())
+    }
+}
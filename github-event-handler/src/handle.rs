@@ -1,12 +1,21 @@
-use crate::api::GitHubApi;
+use crate::action::plan::Target;
 use crate::authentication::InstallationAuthenticator;
+use crate::configuration::{changes_configuration, ConfigurationCache};
+use crate::evaluate::{evaluate_commit, evaluate_event, CommitEvent, Event};
+use crate::lint::lint_configuration_change;
+use crate::membership::MembershipCache;
+use octocrab::models::webhook_events::payload::PullRequestWebhookEventAction;
 use octocrab::models::webhook_events::{
     EventInstallation, WebhookEvent, WebhookEventPayload, WebhookEventType,
 };
+use octocrab::models::Repository;
+use serde::Serialize;
 use snafu::{Backtrace, ResultExt, Snafu};
 
 pub async fn handle_event<C>(
     app_client: C,
+    memberships: &MembershipCache,
+    configurations: &ConfigurationCache,
     event: WebhookEvent,
 ) -> Result<Option<String>, HandleEventError>
 where
@@ -29,22 +38,167 @@ where
             let Some(repository) = event.repository else {
                 return MissingRepositorySnafu.fail();
             };
-            let sha = &pr.pull_request.head.sha;
-            api_client
-                .create_commit_status(&repository, sha)
-                .await
-                .map_err(|err| Box::new(err) as _)
-                .context(EventHandlingSnafu { event: event.kind })?;
+            if matches!(
+                pr.action,
+                PullRequestWebhookEventAction::Opened
+                    | PullRequestWebhookEventAction::Reopened
+                    | PullRequestWebhookEventAction::Synchronize
+            ) {
+                // the lint is advice, it mustn't keep the rules from being evaluated
+                let before = match pr.action {
+                    PullRequestWebhookEventAction::Synchronize => pr.before.as_deref(),
+                    _ => None,
+                };
+                if let Err(err) =
+                    lint_configuration_change(&api_client, &repository, &pr.pull_request, before)
+                        .await
+                {
+                    tracing::warn!(%err, "unable to lint the configuration change");
+                }
+            }
+            let name = event_name("pull_request", &pr.action);
+            let payload = payload(&*pr, &repository);
+            let target = Target {
+                number: pr.number,
+                pull_request: Some(&pr.pull_request),
+            };
+            evaluate_event(
+                &api_client,
+                id,
+                memberships,
+                configurations,
+                &repository,
+                Event {
+                    name: &name,
+                    payload: &payload,
+                    target,
+                },
+            )
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(EventHandlingSnafu { event: event.kind })?;
+            Ok(None)
+        }
+        WebhookEventPayload::Issues(issue) => {
+            let Some(repository) = event.repository else {
+                return MissingRepositorySnafu.fail();
+            };
+            let name = event_name("issues", &issue.action);
+            let payload = payload(&*issue, &repository);
+            let target = Target {
+                number: issue.issue.number,
+                pull_request: None,
+            };
+            evaluate_event(
+                &api_client,
+                id,
+                memberships,
+                configurations,
+                &repository,
+                Event {
+                    name: &name,
+                    payload: &payload,
+                    target,
+                },
+            )
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(EventHandlingSnafu { event: event.kind })?;
+            Ok(None)
+        }
+        WebhookEventPayload::Push(push) => {
+            let Some(repository) = event.repository else {
+                return MissingRepositorySnafu.fail();
+            };
+            if let Some(owner) = &repository.owner {
+                if changes_configuration(&push, &repository) {
+                    tracing::info!(repo = %repository.name, "configuration changed");
+                    configurations.invalidate(id, &owner.login, &repository.name);
+                }
+            }
+            Ok(None)
+        }
+        WebhookEventPayload::PullRequestReview(review) => {
+            let Some(repository) = event.repository else {
+                return MissingRepositorySnafu.fail();
+            };
+            let name = event_name("pull_request_review", &review.action);
+            let payload = payload(&*review, &repository);
+            let target = Target {
+                number: review.pull_request.number,
+                pull_request: Some(&review.pull_request),
+            };
+            evaluate_event(
+                &api_client,
+                id,
+                memberships,
+                configurations,
+                &repository,
+                Event {
+                    name: &name,
+                    payload: &payload,
+                    target,
+                },
+            )
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(EventHandlingSnafu { event: event.kind })?;
+            Ok(None)
+        }
+        WebhookEventPayload::Status(status) => {
+            let Some(repository) = event.repository else {
+                return MissingRepositorySnafu.fail();
+            };
+            // statuses have no action, their state takes its place, e.g. `status.success`
+            let name = event_name("status", &status.state);
+            let payload = payload(&*status, &repository);
+            evaluate_commit(
+                &api_client,
+                id,
+                memberships,
+                configurations,
+                &repository,
+                CommitEvent {
+                    name: &name,
+                    sha: &status.sha,
+                    payload: &payload,
+                },
+            )
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(EventHandlingSnafu { event: event.kind })?;
             Ok(None)
         }
-        WebhookEventPayload::Push(_) => Ok(None),
         WebhookEventPayload::CheckRun(_) => Ok(None),
         WebhookEventPayload::CheckSuite(check) => {
-            let Some(_repository) = event.repository else {
+            let Some(repository) = event.repository else {
                 return MissingRepositorySnafu.fail();
             };
-            // TODO: need to parse check.check_suite or check.enterprise as it's currently just a json object
-            tracing::debug!(check_suite = ?check, "handling check suite");
+            let head_sha = check
+                .check_suite
+                .get("head_sha")
+                .and_then(serde_json::Value::as_str);
+            let Some(head_sha) = head_sha else {
+                tracing::debug!(check_suite = ?check, "check suite without a head");
+                return Ok(None);
+            };
+            let name = event_name("check_suite", &check.action);
+            let payload = payload(&*check, &repository);
+            evaluate_commit(
+                &api_client,
+                id,
+                memberships,
+                configurations,
+                &repository,
+                CommitEvent {
+                    name: &name,
+                    sha: head_sha,
+                    payload: &payload,
+                },
+            )
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(EventHandlingSnafu { event: event.kind })?;
             Ok(None)
         }
         _ => {
@@ -54,6 +208,24 @@ where
     }
 }
 
+/// The event as rules listen to it, e.g. `pull_request.opened`.
+fn event_name(kind: &str, action: &impl Serialize) -> String {
+    let action = serde_json::to_value(action).unwrap_or_default();
+    format!("{kind}.{}", action.as_str().unwrap_or_default())
+}
+
+/// The payload as GitHub sent it, the repository is kept apart from the specific payload.
+fn payload(specific: &impl Serialize, repository: &Repository) -> serde_json::Value {
+    let mut payload = serde_json::to_value(specific).unwrap_or_default();
+    if let Some(payload) = payload.as_object_mut() {
+        payload.insert(
+            "repository".to_owned(),
+            serde_json::to_value(repository).unwrap_or_default(),
+        );
+    }
+    payload
+}
+
 #[derive(Debug, Snafu)]
 pub enum HandleEventError {
     #[snafu(display("Missing installation in the event"))]
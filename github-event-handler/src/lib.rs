@@ -1,3 +1,13 @@
+pub mod action;
 pub mod api;
 pub mod authentication;
+pub mod configuration;
+pub mod context;
+pub mod evaluate;
 pub mod handle;
+pub mod lint;
+pub mod membership;
+pub mod schedule;
+
+#[cfg(test)]
+mod fake;
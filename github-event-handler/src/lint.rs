@@ -0,0 +1,194 @@
+use crate::action::comment::{comment, update_comment};
+use crate::action::ActionError;
+use crate::api::{GitHubApi, RepositoryFile};
+use crate::configuration::CONFIGURATION_PATH;
+use mergeable_compatibility_layer::action::Comment;
+use mergeable_compatibility_layer::configuration::Configuration;
+use octocrab::models::pulls::PullRequest;
+use octocrab::models::Repository;
+use snafu::{ResultExt, Snafu};
+use tracing::instrument;
+
+/// Identifies the lint comment among the comments of the rules.
+const COMMENT_KEY: &str = "configuration lint";
+
+/// Comments the problems of the configuration the pull request changes, before it takes effect.
+///
+/// A configuration without problems isn't worth a comment, unless an earlier lint comment has to
+/// be updated. On a push to the pull request, `before` is its previous head, the configuration is
+/// only linted again if the push changed it.
+#[instrument(skip(api, repository, pull_request), fields(repo = %repository.name, number = pull_request.number))]
+pub async fn lint_configuration_change<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    pull_request: &PullRequest,
+    before: Option<&str>,
+) -> Result<(), LintError> {
+    let head = &pull_request.head.sha;
+    let file = match before {
+        Some(before) => {
+            let file = fetch(api, repository, head).await?;
+            let previous = fetch(api, repository, before).await?;
+            let sha = |file: &Option<RepositoryFile>| file.as_ref().map(|file| file.sha.clone());
+            if sha(&file) == sha(&previous) {
+                return Ok(());
+            }
+            file
+        }
+        None => {
+            let files = api
+                .list_pull_request_files(repository, pull_request.number)
+                .await
+                .map_err(|err| Box::new(err) as _)
+                .context(ApiSnafu)?;
+            if !files.iter().any(|file| file.filename == CONFIGURATION_PATH) {
+                return Ok(());
+            }
+            fetch(api, repository, head).await?
+        }
+    };
+    let Some(file) = file else {
+        return Ok(());
+    };
+    let number = pull_request.number;
+    match report(&file.content) {
+        Some(body) => {
+            let comment_body = Comment {
+                body,
+                leave_old_comment: false,
+            };
+            comment(api, repository, number, COMMENT_KEY, &comment_body)
+                .await
+                .context(CommentSnafu)?;
+        }
+        None => {
+            let body = format!("No problems found in `{CONFIGURATION_PATH}` anymore.");
+            update_comment(api, repository, number, COMMENT_KEY, &body)
+                .await
+                .context(CommentSnafu)?;
+        }
+    }
+    Ok(())
+}
+
+async fn fetch<A: GitHubApi>(
+    api: &A,
+    repository: &Repository,
+    reference: &str,
+) -> Result<Option<RepositoryFile>, LintError> {
+    api.fetch_file_at(repository, CONFIGURATION_PATH, reference)
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(ApiSnafu)
+}
+
+/// The problems of the configuration, `None` if there are none.
+fn report(source: &str) -> Option<String> {
+    let warnings = match Configuration::from_yaml(source) {
+        Ok(configuration) => configuration.lint(),
+        Err(err) => {
+            return Some(format!(
+                "`{CONFIGURATION_PATH}` can't be loaded:\n\n```\n{err}\n```"
+            ));
+        }
+    };
+    if warnings.is_empty() {
+        return None;
+    }
+    let mut body = format!("Problems found in `{CONFIGURATION_PATH}`:\n");
+    for warning in warnings {
+        body.push_str(&format!("\n- `{}`: {}", warning.location, warning.message));
+    }
+    Some(body)
+}
+
+#[derive(Debug, Snafu)]
+pub enum LintError {
+    #[snafu(display("Unable to fetch the changed configuration: {source}"))]
+    Api {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Unable to comment the lint: {source}"))]
+    Comment { source: ActionError },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::CONFIGURATION_PATH;
+    use crate::fake::{self, pull_request, repository, FakeApi, BOT_LOGIN};
+    use crate::lint::lint_configuration_change;
+
+    const VALID: &str = "version: 2\nmergeable:\n  - when: pull_request.*\n";
+    const INVALID: &str = "version: 2\nmergeable: {}\n";
+
+    #[tokio::test]
+    async fn valid_configurations_are_only_commented_to_update_the_lint() {
+        let at = |sha: &str| format!("{CONFIGURATION_PATH}@{sha}");
+        let api = FakeApi {
+            pull_request_files: vec![CONFIGURATION_PATH.to_owned()],
+            ..Default::default()
+        }
+        .with_file("octo/repo", &at("abc"), VALID, "1");
+        let pull_request = pull_request(1, "abc", None);
+
+        lint_configuration_change(&api, &repository("repo"), &pull_request, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            api.calls(),
+            ["files #1", "fetch octo/repo/.github/mergeable.yml@abc"]
+        );
+
+        let marker = "<!-- mergeable-comment: configuration lint -->";
+        let api = FakeApi {
+            comments: vec![fake::comment(7, BOT_LOGIN, &format!("{marker}\nProblems"))],
+            ..api
+        };
+        lint_configuration_change(&api, &repository("repo"), &pull_request, Some("000"))
+            .await
+            .unwrap();
+        assert_eq!(
+            api.calls(),
+            [
+                "fetch octo/repo/.github/mergeable.yml@abc".to_owned(),
+                "fetch octo/repo/.github/mergeable.yml@000".to_owned(),
+                format!(
+                    "update comment 7: {marker}\nNo problems found in `{CONFIGURATION_PATH}` \
+                     anymore."
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn pushes_without_changes_to_the_configuration_are_not_linted_again() {
+        let at = |sha: &str| format!("{CONFIGURATION_PATH}@{sha}");
+        let api = FakeApi::default()
+            .with_file("octo/repo", &at("abc"), INVALID, "1")
+            .with_file("octo/repo", &at("000"), INVALID, "1");
+        let pull_request = pull_request(1, "abc", None);
+
+        lint_configuration_change(&api, &repository("repo"), &pull_request, Some("000"))
+            .await
+            .unwrap();
+        assert_eq!(
+            api.calls(),
+            [
+                "fetch octo/repo/.github/mergeable.yml@abc",
+                "fetch octo/repo/.github/mergeable.yml@000",
+            ]
+        );
+
+        api.put_file("octo/repo", &at("def"), VALID, "2");
+        api.put_file("octo/repo", &at("abc"), INVALID, "3");
+        lint_configuration_change(&api, &repository("repo"), &pull_request, Some("def"))
+            .await
+            .unwrap();
+        let calls = api.calls();
+        assert_eq!(calls.len(), 3);
+        assert!(
+            calls[2].starts_with("comment #1: <!-- mergeable-comment: configuration lint -->\n"),
+            "{calls:?}"
+        );
+    }
+}
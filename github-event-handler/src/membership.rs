@@ -0,0 +1,79 @@
+use octocrab::models::InstallationId;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How long the members of a team are trusted before they are fetched again.
+const TIME_TO_LIVE: Duration = Duration::from_secs(5 * 60);
+
+/// Team members per installation, so every event of a busy repository doesn't query the same teams.
+#[derive(Debug, Default, Clone)]
+pub struct MembershipCache {
+    installations: Arc<Mutex<HashMap<InstallationId, HashMap<String, CachedTeam>>>>,
+}
+
+#[derive(Debug)]
+struct CachedTeam {
+    members: Vec<String>,
+    fetched_at: Instant,
+}
+
+impl MembershipCache {
+    pub fn get(&self, installation: InstallationId, team: &str) -> Option<Vec<String>> {
+        let installations = self
+            .installations
+            .lock()
+            .expect("poisoned membership cache");
+        installations
+            .get(&installation)
+            .and_then(|teams| teams.get(team))
+            .filter(|cached| cached.fetched_at.elapsed() < TIME_TO_LIVE)
+            .map(|cached| cached.members.clone())
+    }
+
+    /// Caches the members of the team, dropping every team whose members expired meanwhile.
+    pub fn insert(&self, installation: InstallationId, team: &str, members: Vec<String>) {
+        let mut installations = self
+            .installations
+            .lock()
+            .expect("poisoned membership cache");
+        installations.retain(|_, teams| {
+            teams.retain(|_, cached| cached.fetched_at.elapsed() < TIME_TO_LIVE);
+            !teams.is_empty()
+        });
+        installations.entry(installation).or_default().insert(
+            team.to_owned(),
+            CachedTeam {
+                members,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::membership::{MembershipCache, TIME_TO_LIVE};
+    use octocrab::models::InstallationId;
+
+    #[tokio::test(start_paused = true)]
+    async fn expired_teams_are_removed_on_insert() {
+        let cache = MembershipCache::default();
+        let members = vec!["octocat".to_owned()];
+        cache.insert(InstallationId(1), "acme/platform", members.clone());
+        cache.insert(InstallationId(2), "acme/billing", members.clone());
+
+        tokio::time::advance(TIME_TO_LIVE).await;
+        assert_eq!(cache.get(InstallationId(1), "acme/platform"), None);
+        cache.insert(InstallationId(1), "acme/web", members.clone());
+
+        let installations = cache.installations.lock().unwrap();
+        assert_eq!(installations.len(), 1);
+        assert_eq!(installations[&InstallationId(1)].len(), 1);
+        assert_eq!(
+            installations[&InstallationId(1)]["acme/web"].members,
+            members
+        );
+    }
+}
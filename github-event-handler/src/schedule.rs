@@ -0,0 +1,260 @@
+use crate::action::plan::{execute, Target};
+use crate::api::GitHubApi;
+use crate::authentication::InstallationAuthenticator;
+use crate::configuration::{resolve_configuration, ConfigurationCache, LoadConfigurationError};
+use crate::context::EventContext;
+use crate::membership::MembershipCache;
+use mergeable_compatibility_layer::action::Plan;
+use mergeable_compatibility_layer::evaluation::template::EventVars;
+use mergeable_compatibility_layer::evaluation::{
+    Clock, EvaluationContext, RuleResult, SystemClock,
+};
+use octocrab::models::{InstallationId, Repository};
+use snafu::{ResultExt, Snafu};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::instrument;
+
+/// The event rules listen to, to be evaluated periodically for every repository.
+pub const SCHEDULE_EVENT: &str = "schedule.repository";
+
+/// Evaluates the scheduled rules of every repository of every installation once per interval.
+///
+/// The caches are the ones of the event handler, which keeps them up to date with the pushes.
+pub async fn schedule<C: InstallationAuthenticator>(
+    app_client: C,
+    interval: Duration,
+    memberships: MembershipCache,
+    configurations: ConfigurationCache,
+) {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        if let Err(err) =
+            run_schedule(&app_client, &memberships, &configurations, &SystemClock).await
+        {
+            tracing::error!(%err, "failed to run the schedule");
+        }
+    }
+}
+
+#[instrument(skip_all)]
+pub async fn run_schedule<C: InstallationAuthenticator>(
+    app_client: &C,
+    memberships: &MembershipCache,
+    configurations: &ConfigurationCache,
+    clock: &dyn Clock,
+) -> Result<(), ScheduleError> {
+    let installations = app_client
+        .list_installations()
+        .await
+        .map_err(|err| Box::new(err) as _)
+        .context(InstallationsSnafu)?;
+    for installation in installations {
+        let api = match app_client.for_installation(installation).await {
+            Ok(api) => api,
+            Err(err) => {
+                tracing::error!(%err, ?installation, "unable to access installation");
+                continue;
+            }
+        };
+        let repositories = match api.list_installation_repositories().await {
+            Ok(repositories) => repositories,
+            Err(err) => {
+                tracing::error!(%err, ?installation, "unable to list the repositories");
+                continue;
+            }
+        };
+        // a single misconfigured repository must not stop the others from being evaluated
+        for repository in &repositories {
+            match run_repository(
+                &api,
+                installation,
+                memberships,
+                configurations,
+                clock,
+                repository,
+            )
+            .await
+            {
+                Ok(results) => {
+                    for result in results {
+                        tracing::info!(repo = %repository.name, ?result, "evaluated scheduled rule");
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(%err, repo = %repository.name, "unable to evaluate the schedule")
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates the rules of the repository listening to [`SCHEDULE_EVENT`].
+#[instrument(skip(api, memberships, configurations, clock, repository), fields(repo = %repository.name))]
+pub async fn run_repository<A: GitHubApi>(
+    api: &A,
+    installation: InstallationId,
+    memberships: &MembershipCache,
+    configurations: &ConfigurationCache,
+    clock: &dyn Clock,
+    repository: &Repository,
+) -> Result<Vec<RuleResult>, ScheduleError> {
+    let Some(configuration) = resolve_configuration(api, installation, configurations, repository)
+        .await
+        .context(ConfigurationSnafu)?
+    else {
+        return Ok(Vec::new());
+    };
+    let payload = serde_json::json!({ "repository": repository });
+    let context =
+        EventContext::new(api, installation, memberships, repository, &payload).with_clock(clock);
+    let now = context.now();
+    let mut evaluated = Vec::new();
+    for compiled in configuration.rules_for(SCHEDULE_EVENT) {
+        let rule = compiled.rule();
+        if !rule.is_active_at(now) {
+            tracing::debug!(rule = ?rule.name(), "outside of the time constraints");
+            continue;
+        }
+        evaluated.push((rule, compiled.evaluate(&context).await));
+    }
+    // the actions of scheduled rules apply to every item their validators found
+    let mut plans: BTreeMap<u64, Plan> = BTreeMap::new();
+    for (rule, result) in &evaluated {
+        let key = configuration.rule_key(rule);
+        for number in result.items() {
+            let event = EventVars {
+                number: Some(number),
+                repository: &repository.name,
+                ..Default::default()
+            };
+            if let Err(err) = plans
+                .entry(number)
+                .or_default()
+                .add(&key, rule, result, event)
+            {
+                tracing::warn!(%err, rule = key, number, "unable to plan the actions of the rule");
+            }
+        }
+    }
+    for (&number, plan) in &plans {
+        let target = Target {
+            number,
+            pull_request: None,
+        };
+        if let Err(err) = execute(api, &context, repository, target, plan).await {
+            tracing::warn!(%err, number, "unable to execute the scheduled actions");
+        }
+    }
+    Ok(evaluated.into_iter().map(|(_, result)| result).collect())
+}
+
+#[derive(Debug, Snafu)]
+pub enum ScheduleError {
+    #[snafu(display("Unable to list the installations: {source}"))]
+    Installations {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Unable to load the configuration: {source}"))]
+    Configuration { source: LoadConfigurationError },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::{ConfigurationCache, CONFIGURATION_PATH};
+    use crate::fake::{repository, FakeApi};
+    use crate::membership::MembershipCache;
+    use crate::schedule::run_repository;
+    use mergeable_compatibility_layer::evaluation::{FixedClock, RuleStatus};
+    use octocrab::models::InstallationId;
+
+    const CONFIGURATION: &str = indoc::indoc! {"
+        version: 2
+        mergeable:
+          - when: schedule.repository
+            name: stale
+            validate:
+              - do: stale
+                days: 20
+                time_constraint:
+                  hours_between: ['9', '17']
+            pass:
+              - do: comment
+                payload:
+                  body: This is stale
+              - do: labels
+                add: stale
+          - when: schedule.repository
+            name: nightly
+            validate:
+              - do: stale
+                time_constraint:
+                  hours_between: ['0', '5']
+            pass:
+              - do: close
+    "};
+
+    #[tokio::test]
+    async fn scheduled_actions_apply_to_every_found_item() {
+        let api = FakeApi {
+            search_results: vec![3, 5],
+            repository_labels: vec!["stale".to_owned()],
+            ..Default::default()
+        }
+        .with_file("octo/repo", CONFIGURATION_PATH, CONFIGURATION, "1");
+        let (memberships, configurations) =
+            (MembershipCache::default(), ConfigurationCache::default());
+        let clock = FixedClock("2024-03-21T10:00:00Z".parse().unwrap());
+
+        let results = run_repository(
+            &api,
+            InstallationId(1),
+            &memberships,
+            &configurations,
+            &clock,
+            &repository("repo"),
+        )
+        .await
+        .unwrap();
+
+        // the nightly rule is outside of its hours
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name.as_deref(), Some("stale"));
+        assert_eq!(results[0].status, RuleStatus::Pass);
+        let marker = "<!-- mergeable-comment: stale -->";
+        assert_eq!(
+            api.calls(),
+            [
+                format!("fetch octo/repo/{CONFIGURATION_PATH}"),
+                "search repo:octo/repo is:open updated:<2024-03-01".to_owned(),
+                format!("comment #3: {marker}\nThis is stale"),
+                "label #3: [\"stale\"]".to_owned(),
+                format!("comment #5: {marker}\nThis is stale"),
+                "label #5: [\"stale\"]".to_owned(),
+            ]
+        );
+
+        let clock = FixedClock("2024-03-21T03:00:00Z".parse().unwrap());
+        let results = run_repository(
+            &api,
+            InstallationId(1),
+            &memberships,
+            &configurations,
+            &clock,
+            &repository("repo"),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name.as_deref(), Some("nightly"));
+        let calls = api.calls();
+        // the configuration is cached, one search, then closing both items
+        assert_eq!(calls.len(), 3, "{calls:?}");
+        assert!(calls[0].starts_with("search "));
+        assert_eq!(calls[1..], ["close #3", "close #5"]);
+    }
+}
@@ -0,0 +1,17 @@
+pub use assign::Assignees;
+pub use checks::{CheckRun, CheckStatus};
+pub use close::{Close, StateReason};
+pub use comment::Comment;
+pub use labels::{LabelChange, LabelDiff};
+pub use merge::{MergeCommit, MergeMethod};
+pub use plan::{Conflict, Plan};
+pub use review::{ReviewRequest, ReviewerSelection, Reviewers, TeamSelection};
+
+pub mod assign;
+pub mod checks;
+pub mod close;
+pub mod comment;
+pub mod labels;
+pub mod merge;
+pub mod plan;
+pub mod review;
@@ -0,0 +1,84 @@
+use crate::configuration::actions::AssignAction;
+
+/// Placeholder for the author of the pull request or issue.
+const AUTHOR: &str = "@author";
+
+/// The logins all assign actions of a plan ask for, each once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Assignees {
+    logins: Vec<String>,
+}
+
+impl Assignees {
+    pub fn add(&mut self, action: &AssignAction) {
+        for login in action
+            .assignees
+            .iter()
+            .flat_map(|assignees| assignees.as_slice())
+        {
+            if !login.is_empty() && !contains(&self.logins, login) {
+                self.logins.push(login.clone());
+            }
+        }
+    }
+
+    /// The logins to assign in addition to the `assigned` ones.
+    pub fn resolve(&self, author: &str, assigned: &[String]) -> Vec<String> {
+        let mut assignees: Vec<String> = Vec::new();
+        for login in &self.logins {
+            let assignee = match login.as_str() {
+                AUTHOR => author,
+                login => login,
+            };
+            if !assignee.is_empty()
+                && !contains(assigned, assignee)
+                && !contains(&assignees, assignee)
+            {
+                assignees.push(assignee.to_owned());
+            }
+        }
+        assignees
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.logins.is_empty()
+    }
+}
+
+fn contains(logins: &[String], login: &str) -> bool {
+    logins.iter().any(|other| other.eq_ignore_ascii_case(login))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::assign::Assignees;
+    use crate::configuration::actions::Action;
+
+    fn assignees(yaml: &[&str]) -> Assignees {
+        let mut assignees = Assignees::default();
+        for yaml in yaml {
+            let Action::Assign(assign) = serde_yaml::from_str(yaml).unwrap() else {
+                panic!("not an assign action");
+            };
+            assignees.add(&assign);
+        }
+        assignees
+    }
+
+    #[test]
+    fn the_author_placeholder_is_resolved_and_existing_assignees_are_skipped() {
+        let assignees = assignees(&[
+            "{do: assign, assignees: ['@author', octocat]}",
+            "{do: assign, assignees: [Octocat, Hubot]}",
+        ]);
+
+        assert_eq!(
+            assignees.resolve("monalisa", &["hubot".to_owned()]),
+            vec!["monalisa".to_owned(), "octocat".to_owned()]
+        );
+        assert_eq!(
+            assignees.resolve("octocat", &[]),
+            vec!["octocat".to_owned(), "Hubot".to_owned()]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::configuration::actions::CheckAction;
+use crate::configuration::template::Template;
+use crate::evaluation::template::{render, RuleVars};
+use crate::evaluation::{EvaluationError, RuleStatus};
+
+/// Names the rule and its outcome.
+const DEFAULT_TITLE: &str = "{{#if name}}{{name}}{{else}}Mergeable{{/if}}: {{validationStatus}}";
+
+/// Lists the validators with their outcome.
+const DEFAULT_SUMMARY: &str = "\
+{{#each validationSuites}}
+- {{statusIcon status}} **{{name}}**
+{{/each}}";
+
+/// Explains every validator, composite ones as the tree of their branches.
+const DEFAULT_TEXT: &str = "\
+{{#each validationSuites}}
+#### {{statusIcon status}} {{name}}
+
+```
+{{description}}
+```
+
+{{/each}}";
+
+/// The conclusion of a check run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Success,
+    Failure,
+    Neutral,
+    Cancelled,
+    TimedOut,
+    ActionRequired,
+}
+
+impl From<RuleStatus> for CheckStatus {
+    fn from(status: RuleStatus) -> Self {
+        match status {
+            RuleStatus::Pass => CheckStatus::Success,
+            RuleStatus::Fail => CheckStatus::Failure,
+            RuleStatus::Error => CheckStatus::ActionRequired,
+            RuleStatus::Skipped => CheckStatus::Neutral,
+        }
+    }
+}
+
+/// The completed check run reporting the outcome of a rule on the head of a pull request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckRun {
+    pub conclusion: CheckStatus,
+    pub title: String,
+    pub summary: String,
+    pub text: String,
+}
+
+impl CheckAction {
+    /// The check run for the outcome of the rule, `status` overrides the conclusion and the
+    /// templates of the `payload` the default output.
+    pub fn check_run(
+        &self,
+        status: RuleStatus,
+        vars: &RuleVars<'_>,
+    ) -> Result<CheckRun, EvaluationError> {
+        let payload = self.payload.as_ref();
+        let output = |template: Option<&Template>, default| match template {
+            Some(template) => template.render(vars),
+            None => render(default, vars),
+        };
+        Ok(CheckRun {
+            conclusion: self.status.unwrap_or(status.into()),
+            title: output(
+                payload.and_then(|payload| payload.title.as_ref()),
+                DEFAULT_TITLE,
+            )?,
+            summary: output(
+                payload.and_then(|payload| payload.summary.as_ref()),
+                DEFAULT_SUMMARY,
+            )?,
+            text: output(
+                payload.and_then(|payload| payload.text.as_ref()),
+                DEFAULT_TEXT,
+            )?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{CheckRun, CheckStatus};
+    use crate::configuration::actions::Action;
+    use crate::evaluation::template::EventVars;
+    use crate::evaluation::{RuleResult, RuleStatus, Status, ValidatorResult};
+
+    #[test]
+    fn check_run_explains_the_composite_validators() {
+        let Action::Checks(checks) = serde_yaml::from_str("do: checks").unwrap() else {
+            panic!("expected the checks action");
+        };
+        let and = ValidatorResult {
+            children: vec![
+                ValidatorResult::pass("milestone", "The milestone is set"),
+                ValidatorResult::fail("project", "project does not include '^roadmap'"),
+            ],
+            ..ValidatorResult::fail(
+                "and",
+                "✔️ milestone: The milestone is set\n❌ project: project does not include '^roadmap'",
+            )
+        };
+        let result = RuleResult {
+            name: Some("ready".into()),
+            status: RuleStatus::Fail,
+            filters: Vec::new(),
+            validators: vec![ValidatorResult::pass("author", "octocat is allowed"), and],
+        };
+        assert_eq!(result.validators[1].status, Status::Fail);
+
+        let check_run = checks
+            .check_run(result.status, &result.vars(EventVars::default()))
+            .unwrap();
+
+        assert_eq!(
+            check_run,
+            CheckRun {
+                conclusion: CheckStatus::Failure,
+                title: "ready: fail".into(),
+                summary: "- ✔️ **author**\n- ❌ **and**\n".into(),
+                text: indoc::indoc! {"
+                    #### ✔️ author
+
+                    ```
+                    octocat is allowed
+                    ```
+
+                    #### ❌ and
+
+                    ```
+                    ✔️ milestone: The milestone is set
+                    ❌ project: project does not include '^roadmap'
+                    ```
+
+                "}
+                .into(),
+            }
+        );
+    }
+
+    #[test]
+    fn configured_payload_is_rendered() {
+        let Action::Checks(checks) = serde_yaml::from_str(indoc::indoc! {"
+            do: checks
+            status: action_required
+            payload:
+              title: '{{toUpperCase validationStatus}} #{{number}}'
+              summary: '{{author}} needs to update {{title}}'
+        "})
+        .unwrap() else {
+            panic!("expected the checks action");
+        };
+        let result = RuleResult {
+            name: None,
+            status: RuleStatus::Fail,
+            filters: Vec::new(),
+            validators: vec![ValidatorResult::fail("title", "The title contains 'wip'")],
+        };
+        let event = EventVars {
+            author: "octocat",
+            title: Some("wip: checks"),
+            number: Some(7),
+            repository: "repo",
+        };
+
+        let check_run = checks
+            .check_run(result.status, &result.vars(event))
+            .unwrap();
+
+        assert_eq!(check_run.conclusion, CheckStatus::ActionRequired);
+        assert_eq!(check_run.title, "FAIL #7");
+        assert_eq!(check_run.summary, "octocat needs to update wip: checks");
+        // the default explanation is kept for what isn't configured
+        assert!(check_run.text.contains("The title contains 'wip'"));
+    }
+}
@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::configuration::actions::CloseAction;
+use crate::evaluation::template::RuleVars;
+use crate::evaluation::EvaluationError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StateReason {
+    #[default]
+    Completed,
+    NotPlanned,
+}
+
+/// Closing as configured, with the comment rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Close {
+    pub state_reason: StateReason,
+    pub comment: Option<String>,
+}
+
+impl CloseAction {
+    pub fn close(&self, vars: &RuleVars<'_>) -> Result<Close, EvaluationError> {
+        Ok(Close {
+            state_reason: self.state_reason.unwrap_or_default(),
+            comment: self
+                .comment
+                .as_ref()
+                .map(|comment| comment.render(vars))
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{Close, StateReason};
+    use crate::configuration::actions::Action;
+    use crate::evaluation::template::EventVars;
+    use crate::evaluation::{RuleResult, RuleStatus};
+
+    #[test]
+    fn closing_comment_is_rendered() {
+        let Action::Close(close) = serde_yaml::from_str(indoc::indoc! {"
+            do: close
+            state_reason: not_planned
+            comment: 'Closing {{name}} after no activity'
+        "})
+        .unwrap() else {
+            panic!("not a close action");
+        };
+        let result = RuleResult {
+            name: Some("stale issues".into()),
+            status: RuleStatus::Fail,
+            filters: Vec::new(),
+            validators: Vec::new(),
+        };
+        let vars = result.vars(EventVars::default());
+
+        assert_eq!(
+            close.close(&vars).unwrap(),
+            Close {
+                state_reason: StateReason::NotPlanned,
+                comment: Some("Closing stale issues after no activity".into()),
+            }
+        );
+    }
+}
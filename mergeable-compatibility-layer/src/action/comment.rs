@@ -0,0 +1,64 @@
+use crate::configuration::actions::CommentAction;
+use crate::evaluation::template::{render, RuleVars};
+use crate::evaluation::EvaluationError;
+
+/// Summarizes the validators when no `payload.body` is configured.
+const DEFAULT_BODY: &str = "\
+{{statusIcon validationStatus}} {{#if name}}{{name}}{{else}}Mergeable{{/if}}: {{validationStatus}}
+{{#each validationSuites}}
+- {{statusIcon status}} **{{name}}**: {{breaklines description}}
+{{/each}}";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    pub body: String,
+    /// Keep the previous comments of the rule instead of replacing them.
+    pub leave_old_comment: bool,
+}
+
+impl CommentAction {
+    pub fn comment(&self, vars: &RuleVars<'_>) -> Result<Comment, EvaluationError> {
+        let body = match self
+            .payload
+            .as_ref()
+            .and_then(|payload| payload.body.as_ref())
+        {
+            Some(body) => body.render(vars)?,
+            None => render(DEFAULT_BODY, vars)?,
+        };
+        Ok(Comment {
+            body,
+            leave_old_comment: self.leave_old_comment.unwrap_or(false),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::actions::Action;
+    use crate::evaluation::template::EventVars;
+    use crate::evaluation::{RuleResult, RuleStatus, ValidatorResult};
+
+    #[test]
+    fn default_body_summarizes_the_validators() {
+        let Action::Comment(comment) = serde_yaml::from_str("do: comment").unwrap() else {
+            panic!("not a comment action");
+        };
+        let result = RuleResult {
+            name: Some("Triage".into()),
+            status: RuleStatus::Fail,
+            filters: Vec::new(),
+            validators: vec![ValidatorResult::fail(
+                "milestone",
+                "The milestone can't be empty",
+            )],
+        };
+
+        let comment = comment.comment(&result.vars(EventVars::default())).unwrap();
+        assert_eq!(
+            comment.body,
+            "❌ Triage: fail\n- ❌ **milestone**: The milestone can't be empty\n"
+        );
+        assert!(!comment.leave_old_comment);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use crate::configuration::actions::LabelsAction;
+use crate::configuration::basics::OneOrMany;
+
+/// The color of created labels without a configured one, GitHub's light gray.
+pub const DEFAULT_COLOR: &str = "ededed";
+
+/// The labels a single action asks for.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelChange {
+    pub replace: Option<Vec<String>>,
+    pub add: Vec<String>,
+    pub delete: Vec<String>,
+    pub create_missing: bool,
+    pub colors: BTreeMap<String, String>,
+}
+
+/// The labels to add to and remove from the pull request or issue.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelDiff {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl LabelsAction {
+    pub fn change(&self) -> LabelChange {
+        let labels = |labels: &Option<OneOrMany<String>>| {
+            labels
+                .iter()
+                .flat_map(OneOrMany::as_slice)
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        LabelChange {
+            replace: self.replace.is_some().then(|| labels(&self.replace)),
+            add: labels(&self.add),
+            delete: labels(&self.delete),
+            create_missing: self.create_missing.unwrap_or(false),
+            colors: self.colors.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl LabelChange {
+    /// Replaces, then adds, then deletes, comparing case-insensitive just like GitHub does.
+    fn apply(&self, labels: &mut Vec<String>) {
+        if let Some(replace) = &self.replace {
+            labels.clear();
+            extend(labels, replace);
+        }
+        extend(labels, &self.add);
+        labels.retain(|label| !contains(&self.delete, label));
+    }
+
+    /// The color to create the label with, if it may be created at all.
+    pub fn color_of(&self, label: &str) -> Option<&str> {
+        if !self.create_missing {
+            return None;
+        }
+        let color = self
+            .colors
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(label))
+            .map_or(DEFAULT_COLOR, |(_, color)| color.as_str());
+        Some(color.trim_start_matches('#'))
+    }
+}
+
+impl LabelDiff {
+    /// Applies the changes of every rule in order and only keeps the net difference, so labels
+    /// which one rule adds and a later one deletes are never touched.
+    pub fn resolve(current: &[String], changes: &[LabelChange]) -> Self {
+        let mut labels = current.to_vec();
+        for change in changes {
+            change.apply(&mut labels);
+        }
+        Self {
+            add: labels
+                .iter()
+                .filter(|label| !contains(current, label))
+                .cloned()
+                .collect(),
+            remove: current
+                .iter()
+                .filter(|label| !contains(&labels, label))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+}
+
+fn contains(labels: &[String], label: &str) -> bool {
+    labels.iter().any(|other| other.eq_ignore_ascii_case(label))
+}
+
+fn extend(labels: &mut Vec<String>, additional: &[String]) {
+    for label in additional {
+        if !contains(labels, label) {
+            labels.push(label.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{LabelChange, LabelDiff};
+    use crate::configuration::actions::Action;
+
+    fn change(yaml: &str) -> LabelChange {
+        let Action::Labels(labels) = serde_yaml::from_str(yaml).unwrap() else {
+            panic!("not a labels action");
+        };
+        labels.change()
+    }
+
+    #[test]
+    fn only_the_net_difference_of_all_rules_is_applied() {
+        let current = vec!["bug".to_owned(), "WIP".to_owned()];
+        let changes = [
+            change("{do: labels, add: 'needs review', delete: wip}"),
+            change("{do: labels, add: [Bug, blocked], create_missing: true, colors: {blocked: '#b60205'}}"),
+            change("{do: labels, delete: blocked}"),
+        ];
+
+        let diff = LabelDiff::resolve(&current, &changes);
+        assert_eq!(
+            diff,
+            LabelDiff {
+                add: vec!["needs review".to_owned()],
+                remove: vec!["WIP".to_owned()],
+            }
+        );
+        assert_eq!(changes[0].color_of("needs review"), None);
+        assert_eq!(changes[1].color_of("Blocked"), Some("b60205"));
+
+        let replaced = LabelDiff::resolve(&current, &[change("{do: labels, replace: [bug]}")]);
+        assert_eq!(replaced.remove, vec!["WIP".to_owned()]);
+        assert!(replaced.add.is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::configuration::actions::MergeAction;
+use crate::evaluation::template::RuleVars;
+use crate::evaluation::EvaluationError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeMethod {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+/// The merge commit as configured, with the templates rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeCommit {
+    pub method: MergeMethod,
+    pub title: Option<String>,
+    pub message: Option<String>,
+}
+
+impl MergeAction {
+    pub fn commit(&self, vars: &RuleVars<'_>) -> Result<MergeCommit, EvaluationError> {
+        Ok(MergeCommit {
+            method: self.merge_method.unwrap_or_default(),
+            title: self
+                .commit_title
+                .as_ref()
+                .map(|title| title.render(vars))
+                .transpose()?,
+            message: self
+                .commit_message
+                .as_ref()
+                .map(|message| message.render(vars))
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{MergeCommit, MergeMethod};
+    use crate::configuration::actions::Action;
+    use crate::evaluation::template::EventVars;
+    use crate::evaluation::{RuleResult, RuleStatus};
+
+    #[test]
+    fn commit_templates_are_rendered() {
+        let Action::Merge(merge) = serde_yaml::from_str(indoc::indoc! {"
+            do: merge
+            merge_method: squash
+            commit_title: '{{title}} (#{{number}})'
+        "})
+        .unwrap() else {
+            panic!("not a merge action");
+        };
+        let result = RuleResult {
+            name: None,
+            status: RuleStatus::Pass,
+            filters: Vec::new(),
+            validators: Vec::new(),
+        };
+        let event = EventVars {
+            title: Some("Add the merge action"),
+            number: Some(35),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            merge.commit(&result.vars(event)).unwrap(),
+            MergeCommit {
+                method: MergeMethod::Squash,
+                title: Some("Add the merge action (#35)".into()),
+                message: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use crate::action::{Assignees, CheckRun, Close, Comment, LabelChange, MergeCommit, Reviewers};
+use crate::configuration::actions::Action;
+use crate::configuration::Rule;
+use crate::evaluation::template::EventVars;
+use crate::evaluation::{EvaluationError, RuleResult};
+
+/// Rules which asked for contradicting actions on the same pull request or issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// Closing wins, as it can be undone while a merge can't.
+    MergeAndClose { merge: String, close: String },
+    /// Differing merge commits, the first rule wins.
+    Merge { kept: String, dropped: String },
+    /// Differing closing comments or reasons, the first rule wins.
+    Close { kept: String, dropped: String },
+    /// The later rule wins, as the label changes are applied in the order of the rules.
+    Label {
+        label: String,
+        added_by: String,
+        deleted_by: String,
+    },
+}
+
+/// All actions the triggered rules ask for on a single pull request or issue.
+///
+/// Rules are added in the order of the configuration, which decides their precedence, so the
+/// same results always lead to the same plan. The plan is executed once, after every rule has
+/// been evaluated.
+#[derive(Debug, Default)]
+pub struct Plan {
+    /// Sticky comments by the key of their rule.
+    pub comments: Vec<(String, Comment)>,
+    /// Check runs on the head of the pull request by the key of their rule.
+    pub checks: Vec<(String, CheckRun)>,
+    pub labels: Vec<LabelChange>,
+    pub assignees: Assignees,
+    pub reviewers: Reviewers,
+    pub merge: Option<MergeCommit>,
+    pub close: Option<Close>,
+    pub conflicts: Vec<Conflict>,
+    merged_by: Option<String>,
+    closed_by: Option<String>,
+    labeled_by: Vec<String>,
+}
+
+impl Plan {
+    /// Plans the actions of the rule for its outcome, `key` identifies the rule.
+    pub fn add(
+        &mut self,
+        key: &str,
+        rule: &Rule,
+        result: &RuleResult,
+        event: EventVars<'_>,
+    ) -> Result<(), EvaluationError> {
+        let vars = result.vars(event);
+        for action in rule.actions(result.status) {
+            match action {
+                Action::Assign(assign) => self.assignees.add(assign),
+                Action::RequestReview(request) => self.reviewers.add(request),
+                Action::Comment(comment) => {
+                    let comment = comment.comment(&vars)?;
+                    // a rule with several comments keeps its latest one
+                    self.comments.retain(|(other, _)| other != key);
+                    self.comments.push((key.to_owned(), comment));
+                }
+                Action::Labels(labels) => self.add_labels(key, labels.change()),
+                Action::Merge(merge) => self.add_merge(key, merge.commit(&vars)?),
+                Action::Close(close) => self.add_close(key, close.close(&vars)?),
+                Action::Checks(checks) => {
+                    let check_run = checks.check_run(result.status, &vars)?;
+                    self.checks.retain(|(other, _)| other != key);
+                    self.checks.push((key.to_owned(), check_run));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_labels(&mut self, key: &str, change: LabelChange) {
+        for (previous, by) in self.labels.iter().zip(&self.labeled_by) {
+            if by == key {
+                continue;
+            }
+            for label in &change.add {
+                if contains(&previous.delete, label) {
+                    self.conflicts.push(Conflict::Label {
+                        label: label.clone(),
+                        added_by: key.to_owned(),
+                        deleted_by: by.clone(),
+                    });
+                }
+            }
+            for label in &change.delete {
+                if contains(&previous.add, label) {
+                    self.conflicts.push(Conflict::Label {
+                        label: label.clone(),
+                        added_by: by.clone(),
+                        deleted_by: key.to_owned(),
+                    });
+                }
+            }
+        }
+        self.labels.push(change);
+        self.labeled_by.push(key.to_owned());
+    }
+
+    fn add_merge(&mut self, key: &str, commit: MergeCommit) {
+        if let Some(close) = &self.closed_by {
+            self.conflicts.push(Conflict::MergeAndClose {
+                merge: key.to_owned(),
+                close: close.clone(),
+            });
+            return;
+        }
+        match (&self.merge, &self.merged_by) {
+            (Some(merge), Some(kept)) if *merge != commit => {
+                self.conflicts.push(Conflict::Merge {
+                    kept: kept.clone(),
+                    dropped: key.to_owned(),
+                });
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                self.merge = Some(commit);
+                self.merged_by = Some(key.to_owned());
+            }
+        }
+    }
+
+    fn add_close(&mut self, key: &str, close: Close) {
+        if let Some(merge) = self.merged_by.take() {
+            self.merge = None;
+            self.conflicts.push(Conflict::MergeAndClose {
+                merge,
+                close: key.to_owned(),
+            });
+        }
+        match (&self.close, &self.closed_by) {
+            (Some(planned), Some(kept)) if *planned != close => {
+                self.conflicts.push(Conflict::Close {
+                    kept: kept.clone(),
+                    dropped: key.to_owned(),
+                });
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                self.close = Some(close);
+                self.closed_by = Some(key.to_owned());
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+            && self.checks.is_empty()
+            && self.labels.is_empty()
+            && self.assignees.is_empty()
+            && self.reviewers.is_empty()
+            && self.merge.is_none()
+            && self.close.is_none()
+    }
+}
+
+fn contains(labels: &[String], label: &str) -> bool {
+    labels.iter().any(|other| other.eq_ignore_ascii_case(label))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{Conflict, Plan};
+    use crate::configuration::Configuration;
+    use crate::evaluation::template::EventVars;
+    use crate::evaluation::{RuleResult, RuleStatus};
+
+    #[test]
+    fn conflicting_actions_follow_the_precedence() {
+        let config = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: pull_request.*
+                name: ready
+                pass:
+                  - do: merge
+                  - do: labels
+                    add: ready
+                    delete: wip
+                  - do: assign
+                    assignees: [octocat]
+                  - do: request_review
+                    reviewers: [hubot]
+              - when: pull_request.*
+                fail:
+                  - do: close
+                  - do: labels
+                    add: wip
+                  - do: checks
+                  - do: assign
+                    assignees: [Octocat, '@author']
+                  - do: request_review
+                    reviewers: [Hubot, monalisa]
+        "})
+        .unwrap()
+        .compile();
+        let mut plan = Plan::default();
+        for (rule, status) in config
+            .rules_for("pull_request.opened")
+            .zip([RuleStatus::Pass, RuleStatus::Fail])
+        {
+            let rule = rule.rule();
+            let result = RuleResult {
+                name: rule.name().map(str::to_owned),
+                status,
+                filters: Vec::new(),
+                validators: Vec::new(),
+            };
+            let key = config.rule_key(rule);
+            plan.add(&key, rule, &result, EventVars::default()).unwrap();
+        }
+
+        assert!(plan.merge.is_none());
+        assert!(plan.close.is_some());
+        assert_eq!(plan.labels.len(), 2);
+        assert_eq!(plan.checks.len(), 1);
+        assert_eq!(plan.checks[0].0, "rule #2");
+        assert_eq!(
+            plan.assignees.resolve("author", &[]),
+            vec!["octocat".to_owned(), "author".to_owned()]
+        );
+        assert_eq!(
+            plan.reviewers.request("author", &[], &[]).reviewers,
+            vec!["hubot".to_owned(), "monalisa".to_owned()]
+        );
+        assert_eq!(
+            plan.conflicts,
+            vec![
+                Conflict::MergeAndClose {
+                    merge: "ready".into(),
+                    close: "rule #2".into(),
+                },
+                Conflict::Label {
+                    label: "wip".into(),
+                    added_by: "rule #2".into(),
+                    deleted_by: "ready".into(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::configuration::actions::RequestReviewAction;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewerSelection {
+    /// Requests the teams themselves.
+    #[default]
+    Teams,
+    /// Rotates through the team members by the number of the pull request.
+    RoundRobin,
+    /// Picks the team members with the fewest open review requests.
+    LeastLoaded,
+}
+
+/// The reviewers and teams all request_review actions of a plan ask for, each once.
+///
+/// A team requested by several actions keeps the selection of the first one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reviewers {
+    reviewers: Vec<String>,
+    teams: Vec<TeamSelection>,
+}
+
+/// How the reviewers of a team are chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TeamSelection {
+    /// The team as `org/team` or just the slug of a team of the repository owner.
+    pub team: String,
+    pub selection: ReviewerSelection,
+    /// How many team members to request.
+    pub count: usize,
+}
+
+/// The reviewers and team slugs to request in addition to the already requested ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReviewRequest {
+    pub reviewers: Vec<String>,
+    pub teams: Vec<String>,
+    /// The author and the reviewers which have been requested before.
+    excluded: Vec<String>,
+}
+
+impl Reviewers {
+    pub fn add(&mut self, action: &RequestReviewAction) {
+        for reviewer in action
+            .reviewers
+            .iter()
+            .flat_map(|reviewers| reviewers.as_slice())
+        {
+            if !contains(&self.reviewers, reviewer) {
+                self.reviewers.push(reviewer.clone());
+            }
+        }
+        for team in action.teams.iter().flat_map(|teams| teams.as_slice()) {
+            if !self
+                .teams
+                .iter()
+                .any(|other| slug(&other.team).eq_ignore_ascii_case(slug(team)))
+            {
+                self.teams.push(TeamSelection {
+                    team: team.clone(),
+                    selection: action.selection.unwrap_or_default(),
+                    count: action.count.unwrap_or(1),
+                });
+            }
+        }
+    }
+
+    /// The teams whose members are selected instead of requesting the team itself.
+    pub fn selected_teams(&self) -> impl Iterator<Item = &TeamSelection> {
+        self.teams
+            .iter()
+            .filter(|team| team.selection != ReviewerSelection::Teams)
+    }
+
+    /// The configured reviewers and teams, skipping the author, who can't review their own pull
+    /// request, and everybody already requested.
+    ///
+    /// The members of the [selected teams](Self::selected_teams) are added by
+    /// [`ReviewRequest::select`].
+    pub fn request(
+        &self,
+        author: &str,
+        requested: &[String],
+        requested_teams: &[String],
+    ) -> ReviewRequest {
+        let mut request = ReviewRequest {
+            excluded: requested
+                .iter()
+                .cloned()
+                .chain([author.to_owned()])
+                .collect(),
+            ..Default::default()
+        };
+        for reviewer in &self.reviewers {
+            if !contains(&request.excluded, reviewer) {
+                request.reviewers.push(reviewer.clone());
+            }
+        }
+        for team in &self.teams {
+            let slug = slug(&team.team);
+            if team.selection == ReviewerSelection::Teams && !contains(requested_teams, slug) {
+                request.teams.push(slug.to_owned());
+            }
+        }
+        request
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reviewers.is_empty() && self.teams.is_empty()
+    }
+}
+
+impl ReviewRequest {
+    /// Requests `team.count` of the team members, the members already requested count towards it.
+    ///
+    /// `load` holds the open review requests of the members, `rotation` the number of the pull
+    /// request to rotate by.
+    pub fn select(
+        &mut self,
+        team: &TeamSelection,
+        members: &[String],
+        rotation: u64,
+        load: &BTreeMap<String, usize>,
+    ) {
+        let mut candidates: Vec<&String> = Vec::new();
+        for member in members {
+            if !contains(&self.excluded, member)
+                && !contains(&self.reviewers, member)
+                && !candidates
+                    .iter()
+                    .any(|other| other.eq_ignore_ascii_case(member))
+            {
+                candidates.push(member);
+            }
+        }
+        candidates.sort();
+        let count = team.count.min(candidates.len());
+        let selected: Vec<&String> = match team.selection {
+            ReviewerSelection::Teams => return,
+            ReviewerSelection::RoundRobin => {
+                let start = (rotation % candidates.len().max(1) as u64) as usize;
+                candidates
+                    .iter()
+                    .cycle()
+                    .skip(start)
+                    .take(count)
+                    .copied()
+                    .collect()
+            }
+            ReviewerSelection::LeastLoaded => {
+                // the sort is stable, so equally loaded members stay in alphabetical order
+                candidates.sort_by_key(|member| load.get(*member).copied().unwrap_or_default());
+                candidates.into_iter().take(count).collect()
+            }
+        };
+        self.reviewers.extend(selected.into_iter().cloned());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reviewers.is_empty() && self.teams.is_empty()
+    }
+}
+
+fn slug(team: &str) -> &str {
+    team.split_once('/').map_or(team, |(_, slug)| slug)
+}
+
+fn contains(logins: &[String], login: &str) -> bool {
+    logins.iter().any(|other| other.eq_ignore_ascii_case(login))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::action::review::Reviewers;
+    use crate::action::ReviewerSelection;
+    use crate::configuration::actions::Action;
+
+    fn reviewers(yaml: &[&str]) -> Reviewers {
+        let mut reviewers = Reviewers::default();
+        for yaml in yaml {
+            let Action::RequestReview(action) = serde_yaml::from_str(yaml).unwrap() else {
+                panic!("not a request_review action");
+            };
+            reviewers.add(&action);
+        }
+        reviewers
+    }
+
+    #[test]
+    fn team_members_are_selected_without_the_author_and_requested_reviewers() {
+        let members: Vec<String> = ["dave", "alice", "carol", "bob"].map(String::from).into();
+        let reviewers = reviewers(&[
+            "{do: request_review, reviewers: [alice, Bob], teams: [acme/core]}",
+            "{do: request_review, reviewers: [bob], teams: [core, docs]}",
+        ]);
+        let request = reviewers.request("alice", &["carol".to_owned()], &["docs".to_owned()]);
+        assert_eq!(request.reviewers, vec!["Bob".to_owned()]);
+        assert_eq!(request.teams, vec!["core".to_owned()]);
+        assert_eq!(reviewers.selected_teams().count(), 0);
+
+        let reviewers = self::reviewers(&[
+            "{do: request_review, reviewers: [alice, Bob], teams: [acme/core], selection: round_robin}",
+            "{do: request_review, teams: [core], selection: least_loaded, count: 2}",
+        ]);
+        let mut round_robin = reviewers.request("alice", &["carol".to_owned()], &[]);
+        assert!(round_robin.teams.is_empty());
+        let teams: Vec<_> = reviewers.selected_teams().collect();
+        assert_eq!(teams.len(), 1);
+        assert_eq!(teams[0].selection, ReviewerSelection::RoundRobin);
+        round_robin.select(teams[0], &members, 42, &BTreeMap::new());
+        assert_eq!(
+            round_robin.reviewers,
+            vec!["Bob".to_owned(), "dave".to_owned()]
+        );
+
+        let reviewers = self::reviewers(&[
+            "{do: request_review, reviewers: [alice, Bob], teams: [acme/core], selection: least_loaded, count: 2}",
+        ]);
+        let mut least_loaded = reviewers.request("alice", &["carol".to_owned()], &[]);
+        let load = BTreeMap::from([("dave".to_owned(), 3), ("eve".to_owned(), 0)]);
+        let members = [members, vec!["eve".to_owned()]].concat();
+        let team = reviewers.selected_teams().next().unwrap();
+        least_loaded.select(team, &members, 42, &load);
+        assert_eq!(
+            least_loaded.reviewers,
+            vec!["Bob".to_owned(), "eve".to_owned(), "dave".to_owned()]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::process::ExitCode;
+
+use mergeable_compatibility_layer::configuration::schema::schema;
+use mergeable_compatibility_layer::configuration::Configuration;
+
+const USAGE: &str = "\
+Usage: mergeable <command>
+
+Commands:
+  schema           Print the JSON Schema of .github/mergeable.yml
+  lint <file>      Report rules which can never pass or never run
+  starlark <file>  Convert the rules to a Starlark script
+  help             Print this message
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["schema"] => {
+            let schema = serde_json::to_string_pretty(&schema()).expect("schemas are plain JSON");
+            println!("{schema}");
+            ExitCode::SUCCESS
+        }
+        ["lint", path] => lint(path),
+        ["starlark", path] => starlark(path),
+        ["help" | "--help" | "-h"] => {
+            print!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        _ => {
+            eprint!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn lint(path: &str) -> ExitCode {
+    let Some(configuration) = load(path) else {
+        return ExitCode::FAILURE;
+    };
+    let warnings = configuration.lint();
+    for warning in &warnings {
+        println!("{path}: {warning}");
+    }
+    if warnings.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn starlark(path: &str) -> ExitCode {
+    let Some(configuration) = load(path) else {
+        return ExitCode::FAILURE;
+    };
+    let conversion = configuration.to_starlark();
+    for unconverted in &conversion.unconverted {
+        eprintln!("{path}: {unconverted}");
+    }
+    print!("{}", conversion.script);
+    ExitCode::SUCCESS
+}
+
+fn load(path: &str) -> Option<Configuration> {
+    let loaded = std::fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|yaml| Configuration::from_yaml(&yaml).map_err(|err| err.to_string()));
+    match loaded {
+        Ok(configuration) => Some(configuration),
+        Err(err) => {
+            eprintln!("{path}: {err}");
+            None
+        }
+    }
+}
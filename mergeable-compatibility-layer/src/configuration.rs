@@ -1,34 +1,144 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub mod actions;
 pub mod basics;
+pub mod compiled;
+pub mod dialect;
 pub mod error;
 pub mod fail;
 pub mod filter;
+pub mod lint;
 pub mod options;
 pub mod pass;
+pub mod pattern;
+pub mod payload;
+pub mod schema;
+pub mod starlark;
+pub mod template;
 pub mod validate;
+pub mod version;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A configuration of `version: 2`, version 1 configurations are upgraded by
+/// [`Configuration::from_yaml`].
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Configuration {
-    version: u32,
-    mergeable: Vec<Rule>,
+    version: version::ConfigurationVersion,
+    /// Repository whose configuration this one is based on, as `owner/repo` or just `repo` of
+    /// the same owner, usually the `.github` repository of the organization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) extends: Option<String>,
+    pub(crate) mergeable: Vec<Rule>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Configuration {
+    pub fn extends(&self) -> Option<&str> {
+        self.extends.as_deref()
+    }
+
+    /// Puts this configuration on top of the `base` one.
+    ///
+    /// Rules with the name of a base rule replace it in place, all other rules are appended.
+    pub fn extend(self, base: Configuration) -> Configuration {
+        let mut rules = base.mergeable;
+        for rule in self.mergeable {
+            let overridden = rule.name.as_ref().and_then(|name| {
+                rules
+                    .iter()
+                    .position(|base| base.name.as_ref() == Some(name))
+            });
+            match overridden {
+                Some(position) => rules[position] = rule,
+                None => rules.push(rule),
+            }
+        }
+        Configuration {
+            version: version::ConfigurationVersion::V2,
+            extends: None,
+            mergeable: rules,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct Rule {
     #[serde(skip_serializing_if = "Option::is_none")]
-    when: Option<String>,
+    pub(crate) when: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<String>,
+    pub(crate) name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    filter: Option<Vec<filter::Filter>>,
+    pub(crate) filter: Option<Vec<filter::Filter>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    validate: Option<Vec<validate::Validator>>,
+    pub(crate) validate: Option<Vec<validate::Validator>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pass: Option<Vec<pass::Pass>>,
+    pub(crate) pass: Option<Vec<pass::Pass>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    fail: Option<Vec<fail::Fail>>,
+    pub(crate) fail: Option<Vec<fail::Fail>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<Vec<error::Error>>,
+    pub(crate) error: Option<Vec<error::Error>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn named_rules_override_the_base_and_others_are_appended() {
+        let base = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: pull_request.*
+                name: title
+                validate:
+                  - do: title
+                    must_exclude:
+                      regex: wip
+              - when: issues.opened
+                name: triage
+        "})
+        .unwrap();
+        let config = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            extends: acme/.github
+            mergeable:
+              - when: pull_request.opened
+                name: title
+              - when: pull_request.*
+        "})
+        .unwrap();
+        assert_eq!(config.extends(), Some("acme/.github"));
+
+        let config = config.extend(base);
+        let rules: Vec<_> = config
+            .mergeable
+            .iter()
+            .map(|rule| (rule.name(), rule.when.as_deref()))
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                (Some("title"), Some("pull_request.opened")),
+                (Some("triage"), Some("issues.opened")),
+                (None, Some("pull_request.*")),
+            ]
+        );
+        assert_eq!(config.extends(), None);
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected_when_loaded() {
+        let err = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: pull_request.*
+                pass:
+                  - do: merge
+                    merge_method: fast_forward
+        "})
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("unknown variant `fast_forward`"),
+            "{err}"
+        );
+    }
 }
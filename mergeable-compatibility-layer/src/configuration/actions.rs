@@ -1,103 +1,133 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::basics::OneOrMany;
+use super::template::Template;
+use crate::action::{CheckStatus, MergeMethod, ReviewerSelection, StateReason};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct AssignAction {
+    /// Logins to assign, `@author` stands for the author of the pull request or issue.
     #[serde(rename = "assignees")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    assignees: Option<OneOrMany<String>>,
+    pub(crate) assignees: Option<OneOrMany<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct CheckAction {
     #[serde(rename = "status")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    status: Option<String>,
+    pub(crate) status: Option<CheckStatus>,
     #[serde(rename = "payload")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    payload: Option<Payload>,
+    pub(crate) payload: Option<Payload>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Payload {
     #[serde(rename = "title")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    title: Option<String>,
+    pub(crate) title: Option<Template>,
     #[serde(rename = "summary")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    summary: Option<String>,
+    pub(crate) summary: Option<Template>,
     #[serde(rename = "text")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    text: Option<String>,
+    pub(crate) text: Option<Template>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct CloseAction;
+pub struct CloseAction {
+    /// Only issues carry a reason.
+    #[serde(rename = "state_reason")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) state_reason: Option<StateReason>,
+    /// Commented before closing.
+    #[serde(rename = "comment")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) comment: Option<Template>,
+}
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct CommentAction {
     #[serde(rename = "payload")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    payload: Option<CommentPayload>,
+    pub(crate) payload: Option<CommentPayload>,
     #[serde(rename = "leave_old_comment")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    leave_old_comment: Option<bool>,
+    pub(crate) leave_old_comment: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct CommentPayload {
     #[serde(rename = "body")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    body: Option<String>,
+    pub(crate) body: Option<Template>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct MergeAction {
     #[serde(rename = "merge_method")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    merge_method: Option<String>,
+    pub(crate) merge_method: Option<MergeMethod>,
     #[serde(rename = "commit_title")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    commit_title: Option<String>,
+    pub(crate) commit_title: Option<Template>,
     #[serde(rename = "commit_message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    commit_message: Option<String>,
+    pub(crate) commit_message: Option<Template>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct LabelsAction {
     #[serde(rename = "add")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    add: Option<OneOrMany<String>>,
+    pub(crate) add: Option<OneOrMany<String>>,
     #[serde(rename = "delete")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    delete: Option<OneOrMany<String>>,
+    pub(crate) delete: Option<OneOrMany<String>>,
     #[serde(rename = "replace")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    replace: Option<OneOrMany<String>>,
+    pub(crate) replace: Option<OneOrMany<String>>,
+    /// Create labels which don't exist in the repository yet, instead of skipping them.
+    #[serde(rename = "create_missing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) create_missing: Option<bool>,
+    /// Hex colors of the created labels, e.g. `'needs review': 'fbca04'`.
+    #[serde(rename = "colors")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) colors: Option<BTreeMap<String, String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct RequestReviewAction {
     #[serde(rename = "reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    reviewers: Option<OneOrMany<String>>,
+    pub(crate) reviewers: Option<OneOrMany<String>>,
     #[serde(rename = "teams")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    teams: Option<OneOrMany<String>>,
+    pub(crate) teams: Option<OneOrMany<String>>,
+    /// Requests `count` members of the teams instead of the teams themselves.
+    #[serde(rename = "selection")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) selection: Option<ReviewerSelection>,
+    #[serde(rename = "count")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) count: Option<usize>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "do")]
 #[serde(deny_unknown_fields)]
 pub enum Action {
@@ -123,3 +153,63 @@ pub enum Action {
     #[serde(rename = "request_review")]
     RequestReview(RequestReviewAction),
 }
+
+impl Action {
+    /// The name of the action as used by the `do` key of the configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Assign(_) => "assign",
+            Action::Checks(_) => "checks",
+            Action::Close(_) => "close",
+            Action::Comment(_) => "comment",
+            Action::Merge(_) => "merge",
+            Action::Labels(_) => "labels",
+            Action::RequestReview(_) => "request_review",
+        }
+    }
+
+    /// The events the action can be executed for.
+    pub fn supported_events(&self) -> &'static [&'static str] {
+        const SCHEDULE_PULL_REQUEST_AND_ISSUES: &[&str] =
+            &["schedule.repository", "pull_request.*", "issues.*"];
+        match self {
+            Action::Assign(_) => &["pull_request.*", "issues.*"],
+            Action::Checks(_) => &[
+                "pull_request.assigned",
+                "pull_request.auto_merge_disabled",
+                "pull_request.auto_merge_enabled",
+                "pull_request.converted_to_draft",
+                "pull_request.demilestoned",
+                "pull_request.dequeued",
+                "pull_request.edited",
+                "pull_request.enqueued",
+                "pull_request.labeled",
+                "pull_request.locked",
+                "pull_request.milestoned",
+                "pull_request.opened",
+                "pull_request.push_synchronize",
+                "pull_request.ready_for_review",
+                "pull_request.reopened",
+                "pull_request.review_request_removed",
+                "pull_request.review_requested",
+                "pull_request.synchronize",
+                "pull_request.unassigned",
+                "pull_request.unlabeled",
+                "pull_request.unlocked",
+                "pull_request_review.dismissed",
+                "pull_request_review.edited",
+                "pull_request_review.submitted",
+            ],
+            Action::Close(_) | Action::Comment(_) | Action::Labels(_) => {
+                SCHEDULE_PULL_REQUEST_AND_ISSUES
+            }
+            Action::Merge(_) => &[
+                "pull_request.*",
+                "pull_request_review.*",
+                "status.*",
+                "check_suite.*",
+            ],
+            Action::RequestReview(_) => &["pull_request.*"],
+        }
+    }
+}
@@ -1,19 +1,83 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
 
 use crate::configuration::options::{BeginsWith, EndsWith, MustExclude, MustInclude};
-use serde::{Deserialize, Serialize};
+use crate::configuration::pattern::{Pattern, RegexFlags};
+use crate::configuration::template::Template;
+use schemars::JsonSchema;
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{self, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Deserialized by hand instead of `untagged`, which would hide why the value is invalid.
+#[derive(Debug, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum OneOrMany<T: Debug> {
     Many(Vec<T>),
     Single(T),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct MessageClause(String);
+impl<'de, T: Debug + Deserialize<'de>> Deserialize<'de> for OneOrMany<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OneOrManyVisitor<T>(PhantomData<T>);
 
-#[derive(Debug, Deserialize, Serialize)]
+        impl<'de, T: Debug + Deserialize<'de>> Visitor<'de> for OneOrManyVisitor<T> {
+            type Value = OneOrMany<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a value or a list of values")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(SeqAccessDeserializer::new(seq)).map(OneOrMany::Many)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                T::deserialize(MapAccessDeserializer::new(map)).map(OneOrMany::Single)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                T::deserialize(value.into_deserializer()).map(OneOrMany::Single)
+            }
+        }
+
+        deserializer.deserialize_any(OneOrManyVisitor(PhantomData))
+    }
+}
+
+impl<T: Debug> OneOrMany<T> {
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            OneOrMany::Many(many) => many,
+            OneOrMany::Single(single) => std::slice::from_ref(single),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MessageClause(pub(crate) Template);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
 pub enum MatchClause {
@@ -27,89 +91,141 @@ pub enum MatchClause {
     Short(String),
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// A regex clause, its regexes are compiled with its flags when the configuration is compiled.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct RegexClause {
+    pub(crate) form: RegexForm,
+}
+
+/// The regexes of a clause with their flags and message, or just a single regex.
+///
+/// Deserialized by hand instead of `untagged`, which would hide why the value is invalid.
+#[derive(Debug, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
-pub enum RegexClause {
+pub enum RegexForm {
     Long {
         #[serde(rename = "regex")]
-        regex: OneOrMany<String>,
+        regex: OneOrMany<Pattern>,
         #[serde(skip_serializing_if = "Option::is_none")]
         #[serde(rename = "regex_flag")]
-        regex_flag: Option<String>,
+        regex_flag: Option<RegexFlags>,
         #[serde(rename = "message")]
         #[serde(skip_serializing_if = "Option::is_none")]
         message: Option<MessageClause>,
     },
-    Short(String),
+    Short(Pattern),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LongRegexClause {
+    regex: OneOrMany<Pattern>,
+    regex_flag: Option<RegexFlags>,
+    message: Option<MessageClause>,
+}
+
+impl<'de> Deserialize<'de> for RegexClause {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RegexClauseVisitor;
+
+        impl<'de> Visitor<'de> for RegexClauseVisitor {
+            type Value = RegexForm;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a regex or a map with 'regex'")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                let LongRegexClause {
+                    regex,
+                    regex_flag,
+                    message,
+                } = Deserialize::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(RegexForm::Long {
+                    regex,
+                    regex_flag,
+                    message,
+                })
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(RegexForm::Short(Pattern::from(value)))
+            }
+        }
+
+        let form = deserializer.deserialize_any(RegexClauseVisitor)?;
+        Ok(RegexClause { form })
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct CountClause {
     #[serde(rename = "count")]
-    count: u64,
+    pub(crate) count: u64,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TimeClause {
     #[serde(rename = "days")]
-    days: u32,
+    pub(crate) days: u32,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct BooleanClause {
     #[serde(rename = "match")]
-    match_clause: bool,
+    pub(crate) match_clause: bool,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ChainedAndOrIncludeExcludeClause {
     #[serde(rename = "and")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<Self>>,
+    pub(crate) and: Option<Vec<Self>>,
     #[serde(rename = "or")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<Self>>,
+    pub(crate) or: Option<Vec<Self>>,
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ChainedAndOrIncludeExcludeClauseBeginsEnds {
     #[serde(rename = "and")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<Self>>,
+    pub(crate) and: Option<Vec<Self>>,
     #[serde(rename = "or")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<Self>>,
+    pub(crate) or: Option<Vec<Self>>,
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
+    pub(crate) begins_with: Option<BeginsWith>,
     #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) ends_with: Option<EndsWith>,
 }
@@ -0,0 +1,582 @@
+use std::collections::HashMap;
+
+use globset::GlobSet;
+use snafu::{OptionExt, Snafu};
+
+use super::filter::Filter;
+use super::lint::Chain;
+use super::options::{Jira, MustExclude, MustInclude};
+use super::pattern::{Pattern, PatternError, RegexFlags, Regexes};
+use super::payload::PayloadField;
+use super::validate::{self, InvalidGlobError, Validator};
+use super::{Configuration, Rule};
+use crate::evaluation::{
+    EvaluationContext, EvaluationError, NotCompiledSnafu, RuleResult, RuleStatus, ValidatorResult,
+};
+
+/// A configuration prepared for evaluating many events.
+///
+/// The regexes and globs of the rules are compiled once, a rule with one which can't be compiled
+/// reports an error instead of failing the whole configuration. The rules are indexed by the
+/// events they listen to, so looking them up is a single map access.
+#[derive(Debug)]
+pub struct CompiledConfiguration {
+    configuration: Configuration,
+    compiled: Compiled,
+    /// The patterns which can't be compiled, by the position of their rule.
+    errors: HashMap<usize, Vec<CompileError>>,
+    /// Positions of the rules listening to an event like `pull_request.opened`, including the
+    /// ones listening to all events of its kind.
+    events: HashMap<String, Vec<usize>>,
+    /// Positions of the rules listening to all events of a kind, `pull_request` for
+    /// `pull_request.*`.
+    kinds: HashMap<String, Vec<usize>>,
+}
+
+impl Configuration {
+    pub fn compile(self) -> CompiledConfiguration {
+        let mut compiled = Compiled::default();
+        let mut errors = HashMap::new();
+        let mut events: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut kinds: HashMap<String, Vec<usize>> = HashMap::new();
+        for (position, rule) in self.mergeable.iter().enumerate() {
+            let rule_errors = compiled.rule(&format!("mergeable[{position}]"), rule);
+            if !rule_errors.is_empty() {
+                errors.insert(position, rule_errors);
+            }
+            for event in rule.events() {
+                let index = match event.strip_suffix(".*") {
+                    Some(kind) => kinds.entry(kind.to_owned()).or_default(),
+                    None => events.entry(event.to_owned()).or_default(),
+                };
+                if index.last() != Some(&position) {
+                    index.push(position);
+                }
+            }
+        }
+        for (event, positions) in &mut events {
+            let Some(all) = event.split_once('.').and_then(|(kind, _)| kinds.get(kind)) else {
+                continue;
+            };
+            positions.extend(all);
+            positions.sort_unstable();
+            positions.dedup();
+        }
+        CompiledConfiguration {
+            configuration: self,
+            compiled,
+            errors,
+            events,
+            kinds,
+        }
+    }
+}
+
+impl CompiledConfiguration {
+    pub fn configuration(&self) -> &Configuration {
+        &self.configuration
+    }
+
+    /// The rules listening to the event, e.g. `pull_request.opened`, in the configured order.
+    pub fn rules_for<'a>(&'a self, event: &str) -> impl Iterator<Item = CompiledRule<'a>> + 'a {
+        let positions = self.events.get(event).or_else(|| {
+            event
+                .split_once('.')
+                .and_then(|(kind, _)| self.kinds.get(kind))
+        });
+        positions
+            .map_or(&[][..], Vec::as_slice)
+            .iter()
+            .map(|&position| CompiledRule {
+                rule: &self.configuration.mergeable[position],
+                compiled: &self.compiled,
+                errors: self.errors.get(&position).map_or(&[][..], Vec::as_slice),
+            })
+    }
+
+    /// Identifies the rule across events, by its name or else its position.
+    pub fn rule_key(&self, rule: &Rule) -> String {
+        self.configuration.rule_key(rule)
+    }
+}
+
+/// A rule of a [`CompiledConfiguration`], evaluated with the regexes and globs compiled for it.
+#[derive(Debug, Clone, Copy)]
+pub struct CompiledRule<'a> {
+    rule: &'a Rule,
+    compiled: &'a Compiled,
+    errors: &'a [CompileError],
+}
+
+impl<'a> CompiledRule<'a> {
+    pub fn rule(&self) -> &'a Rule {
+        self.rule
+    }
+
+    /// Evaluates the rule, or reports the patterns which can't be compiled as its error.
+    pub async fn evaluate<C: EvaluationContext>(&self, context: &C) -> RuleResult {
+        if self.errors.is_empty() {
+            return self.rule.evaluate(context, self.compiled).await;
+        }
+        RuleResult {
+            name: self.rule.name.clone(),
+            status: RuleStatus::Error,
+            filters: Vec::new(),
+            validators: self
+                .errors
+                .iter()
+                .map(|err| ValidatorResult::error(err.validator(), err.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// The regexes and globs of the rules, looked up by the clauses they were compiled for.
+#[derive(Debug, Default)]
+pub struct Compiled {
+    /// By the flags of a clause and its patterns, clauses with the same ones share them.
+    regexes: HashMap<RegexFlags, HashMap<Vec<Pattern>, Regexes>>,
+    /// By their patterns, a single glob is a set of one.
+    globs: HashMap<Vec<String>, GlobSet>,
+}
+
+impl Compiled {
+    pub(crate) fn regexes(
+        &self,
+        patterns: &[Pattern],
+        flags: &RegexFlags,
+    ) -> Result<&Regexes, EvaluationError> {
+        self.regexes
+            .get(flags)
+            .and_then(|regexes| regexes.get(patterns))
+            .with_context(|| NotCompiledSnafu {
+                patterns: joined(patterns.iter().map(Pattern::as_str)),
+            })
+    }
+
+    pub(crate) fn globs(&self, patterns: &[String]) -> Result<&GlobSet, EvaluationError> {
+        self.globs.get(patterns).with_context(|| NotCompiledSnafu {
+            patterns: joined(patterns.iter().map(String::as_str)),
+        })
+    }
+
+    /// Compiles the patterns of the rule at the location, like `mergeable[0]`, returning the
+    /// ones which can't be compiled.
+    pub(crate) fn rule(&mut self, location: &str, rule: &Rule) -> Vec<CompileError> {
+        let mut compiler = Compiler {
+            compiled: self,
+            validator: "",
+            errors: Vec::new(),
+        };
+        compiler.rule(location, rule);
+        compiler.errors
+    }
+}
+
+fn joined<'a>(patterns: impl Iterator<Item = &'a str>) -> String {
+    patterns.collect::<Vec<_>>().join("', '")
+}
+
+/// Walks the filters and validators of a rule like the lint does, compiling their patterns.
+struct Compiler<'a> {
+    compiled: &'a mut Compiled,
+    /// The top level filter or validator being compiled.
+    validator: &'static str,
+    errors: Vec<CompileError>,
+}
+
+impl Compiler<'_> {
+    fn rule(&mut self, location: &str, rule: &Rule) {
+        for (position, filter) in rule.filter.iter().flatten().enumerate() {
+            self.validator = filter.name();
+            self.filter(&format!("{location}.filter[{position}]"), filter);
+        }
+        for (position, validator) in rule.validate.iter().flatten().enumerate() {
+            self.validator = validator.name();
+            self.validator(&format!("{location}.validate[{position}]"), validator);
+        }
+    }
+
+    fn filter(&mut self, location: &str, filter: &Filter) {
+        match filter {
+            Filter::Author(author) => self.chain(location, &author.filter),
+            Filter::Repository(repository) => {
+                for (name, chain) in [("name", &repository.name), ("topics", &repository.topics)] {
+                    if let Some(chain) = chain {
+                        self.chain(&format!("{location}.{name}"), chain);
+                    }
+                }
+            }
+            Filter::Payload(payload) => {
+                for (name, field) in [
+                    ("pull_request", &payload.pull_request),
+                    ("review", &payload.review),
+                    ("sender", &payload.sender),
+                ] {
+                    if let Some(field) = field {
+                        self.payload(&format!("{location}.{name}"), field);
+                    }
+                }
+            }
+            Filter::And(and) => self.filters(location, &and.0.filter),
+            Filter::Or(or) => self.filters(location, &or.0.filter),
+            Filter::Not(not) => self.filters(location, &not.0.filter),
+        }
+    }
+
+    fn filters(&mut self, location: &str, filters: &[Filter]) {
+        for (position, filter) in filters.iter().enumerate() {
+            self.filter(&format!("{location}.filter[{position}]"), filter);
+        }
+    }
+
+    fn payload(&mut self, location: &str, field: &PayloadField) {
+        match field {
+            PayloadField::Nested(fields) => {
+                for (name, field) in fields {
+                    self.payload(&format!("{location}.{name}"), field);
+                }
+            }
+            PayloadField::Check(check) => {
+                for (name, clause) in [
+                    ("must_include", &check.include),
+                    ("must_exclude", &check.exclude),
+                ] {
+                    if let Some(clause) = clause {
+                        self.regexes(
+                            &format!("{location}.{name}"),
+                            clause.regex.as_slice(),
+                            clause.regex_flag.clone().unwrap_or_default(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn validator(&mut self, location: &str, validator: &Validator) {
+        match validator {
+            Validator::Author(author) => self.chain(location, &author.author),
+            Validator::BaseRef(base) => {
+                self.include_exclude(location, base.include.as_ref(), base.exclude.as_ref());
+            }
+            Validator::ChangeSet(changeset) => self.chain(location, &changeset.changeset),
+            Validator::Commit(commit) => {
+                if let Some(regex) = commit
+                    .message
+                    .as_ref()
+                    .and_then(|message| message.regex.as_ref())
+                {
+                    self.regexes(
+                        &format!("{location}.message"),
+                        std::slice::from_ref(regex),
+                        RegexFlags::default(),
+                    );
+                }
+                self.jira(location, commit.jira.as_ref());
+            }
+            Validator::Contents(contents) => self.include_exclude(
+                location,
+                contents.content.include.as_ref(),
+                contents.content.exclude.as_ref(),
+            ),
+            Validator::Dependent(dependent) => {
+                if let Some(files) = &dependent.files {
+                    self.globs(&format!("{location}.files"), files);
+                }
+                if let Some(changed) = &dependent.changed {
+                    if let Some(file) = &changed.file {
+                        self.globs(
+                            &format!("{location}.changed.file"),
+                            std::slice::from_ref(file),
+                        );
+                    }
+                    if let Some(files) = &changed.files {
+                        self.globs(&format!("{location}.changed.files"), files);
+                    }
+                }
+            }
+            Validator::Description(description) => {
+                if let Some(chain) = &description.description {
+                    self.chain(location, chain);
+                }
+                self.jira(location, description.jira.as_ref());
+            }
+            Validator::HeadRef(head) => {
+                self.include_exclude(location, head.include.as_ref(), head.exclude.as_ref());
+                self.jira(location, head.jira.as_ref());
+            }
+            Validator::Label(label) => {
+                self.chain(location, &label.label);
+                self.jira(location, label.jira.as_ref());
+            }
+            Validator::Milestone(milestone) => {
+                self.chain(location, &milestone.milestone);
+                self.jira(location, milestone.jira.as_ref());
+            }
+            Validator::Project(project) => {
+                self.include_exclude(location, project.include.as_ref(), None);
+            }
+            Validator::Title(title) => {
+                self.chain(location, &title.title);
+                self.jira(location, title.jira.as_ref());
+            }
+            Validator::And(and) => self.validators(location, &and.0.validate),
+            Validator::Or(or) => self.validators(location, &or.0.validate),
+            Validator::Not(not) => self.validators(location, &not.0.validate),
+            Validator::Age(_)
+            | Validator::Approvals(_)
+            | Validator::Assignee(_)
+            | Validator::Size(_)
+            | Validator::Stale(_) => {}
+        }
+    }
+
+    fn validators(&mut self, location: &str, validators: &[Validator]) {
+        for (position, validator) in validators.iter().enumerate() {
+            self.validator(&format!("{location}.validate[{position}]"), validator);
+        }
+    }
+
+    fn chain(&mut self, location: &str, chain: &impl Chain) {
+        let clauses = chain.clauses();
+        self.include_exclude(location, clauses.include, clauses.exclude);
+        for (operator, chains) in chain.operands() {
+            for (position, chain) in chains.iter().enumerate() {
+                self.chain(&format!("{location}.{operator}[{position}]"), chain);
+            }
+        }
+    }
+
+    fn include_exclude(
+        &mut self,
+        location: &str,
+        include: Option<&MustInclude>,
+        exclude: Option<&MustExclude>,
+    ) {
+        for (name, clause) in [
+            ("must_include", include.map(|include| &include.0)),
+            ("must_exclude", exclude.map(|exclude| &exclude.0)),
+        ] {
+            if let Some(clause) = clause {
+                let (patterns, flags) = clause.patterns();
+                self.regexes(&format!("{location}.{name}"), patterns, flags);
+            }
+        }
+    }
+
+    fn jira(&mut self, location: &str, jira: Option<&Jira>) {
+        if let Some(jira) = jira {
+            let (patterns, flags) = jira.0.patterns();
+            self.regexes(&format!("{location}.jira"), patterns, flags);
+        }
+    }
+
+    fn regexes(&mut self, location: &str, patterns: &[Pattern], flags: RegexFlags) {
+        let compiled = self.compiled.regexes.get(&flags);
+        if compiled.is_some_and(|compiled| compiled.contains_key(patterns)) {
+            return;
+        }
+        match Regexes::compile(patterns, &flags) {
+            Ok(regexes) => {
+                let compiled = self.compiled.regexes.entry(flags).or_default();
+                compiled.insert(patterns.to_vec(), regexes);
+            }
+            Err(source) => self.errors.push(CompileError::Regex {
+                validator: self.validator,
+                location: location.to_owned(),
+                source,
+            }),
+        }
+    }
+
+    fn globs(&mut self, location: &str, patterns: &[String]) {
+        if self.compiled.globs.contains_key(patterns) {
+            return;
+        }
+        match validate::glob_set(patterns) {
+            Ok(globs) => {
+                self.compiled.globs.insert(patterns.to_vec(), globs);
+            }
+            Err(source) => self.errors.push(CompileError::Glob {
+                validator: self.validator,
+                location: location.to_owned(),
+                source,
+            }),
+        }
+    }
+}
+
+/// A regex or glob of a rule which can't be compiled.
+#[derive(Debug, Snafu)]
+pub enum CompileError {
+    #[snafu(display("{location}: {source}"))]
+    Regex {
+        validator: &'static str,
+        location: String,
+        source: PatternError,
+    },
+    #[snafu(display("{location}: {source}"))]
+    Glob {
+        validator: &'static str,
+        location: String,
+        source: InvalidGlobError,
+    },
+}
+
+impl CompileError {
+    /// The top level filter or validator of the pattern.
+    pub fn validator(&self) -> &'static str {
+        match self {
+            CompileError::Regex { validator, .. } | CompileError::Glob { validator, .. } => {
+                validator
+            }
+        }
+    }
+
+    /// Where in the configuration, like `mergeable[0].validate[1].must_exclude`.
+    pub fn location(&self) -> &str {
+        match self {
+            CompileError::Regex { location, .. } | CompileError::Glob { location, .. } => location,
+        }
+    }
+
+    /// Why the pattern can't be compiled, without its location.
+    pub fn reason(&self) -> String {
+        match self {
+            CompileError::Regex { source, .. } => source.to_string(),
+            CompileError::Glob { source, .. } => source.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Compiled {
+    /// The regexes and globs of a single validator, which have to compile.
+    pub(crate) fn of_validator(validator: &Validator) -> Self {
+        let mut compiled = Self::default();
+        let mut compiler = Compiler {
+            compiled: &mut compiled,
+            validator: validator.name(),
+            errors: Vec::new(),
+        };
+        compiler.validator("validate[0]", validator);
+        assert!(compiler.errors.is_empty(), "{:?}", compiler.errors);
+        compiled
+    }
+
+    /// The regexes of a single filter, which have to compile.
+    pub(crate) fn of_filter(filter: &Filter) -> Self {
+        let mut compiled = Self::default();
+        let mut compiler = Compiler {
+            compiled: &mut compiled,
+            validator: filter.name(),
+            errors: Vec::new(),
+        };
+        compiler.filter("filter[0]", filter);
+        assert!(compiler.errors.is_empty(), "{:?}", compiler.errors);
+        compiled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::Configuration;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::{RuleStatus, Status};
+
+    #[test]
+    fn rules_are_looked_up_by_event_in_configured_order() {
+        let config = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: pull_request.opened
+                name: first
+              - when: issues.*, pull_request.*
+                name: second
+              - when: pull_request.opened, pull_request.edited, pull_request.*
+                name: third
+              - when: schedule.repository
+                name: fourth
+        "})
+        .unwrap()
+        .compile();
+
+        let names = |event| -> Vec<_> {
+            config
+                .rules_for(event)
+                .map(|rule| rule.rule().name().unwrap())
+                .collect()
+        };
+        assert_eq!(names("pull_request.opened"), ["first", "second", "third"]);
+        assert_eq!(names("pull_request.closed"), ["second", "third"]);
+        assert_eq!(names("issues.opened"), ["second"]);
+        assert_eq!(names("schedule.repository"), ["fourth"]);
+        assert!(names("check_suite.completed").is_empty());
+    }
+
+    #[tokio::test]
+    async fn rules_with_patterns_which_cant_be_compiled_report_an_error() {
+        let config = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: pull_request.opened
+                name: title
+                validate:
+                  - do: title
+                    must_exclude:
+                      regex: ['wip', '(dnm']
+                      regex_flag: i
+                  - do: dependent
+                    files: ['src/[a']
+              - when: pull_request.opened
+                name: author
+                validate:
+                  - do: author
+                    must_exclude:
+                      regex: 'wip'
+                      regex_flag: i
+        "})
+        .unwrap();
+        let warnings: Vec<_> = config
+            .lint()
+            .iter()
+            .map(|warning| warning.location.clone())
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "mergeable[0].validate[0].must_exclude",
+                "mergeable[0].validate[1].files"
+            ]
+        );
+        let config = config.compile();
+        let context = TestContext {
+            author: "octocat".into(),
+            ..Default::default()
+        };
+
+        let rules: Vec<_> = config.rules_for("pull_request.opened").collect();
+        let result = rules[0].evaluate(&context).await;
+        assert_eq!(result.status, RuleStatus::Error);
+        let errors: Vec<_> = result
+            .validators
+            .iter()
+            .map(|validator| (validator.name, validator.status))
+            .collect();
+        assert_eq!(
+            errors,
+            [("title", Status::Error), ("dependent", Status::Error)]
+        );
+        assert!(
+            result.validators[0]
+                .description
+                .starts_with("mergeable[0].validate[0].must_exclude: Invalid regex '(dnm'"),
+            "{}",
+            result.validators[0].description
+        );
+        // the other rules still evaluate, sharing the regexes of equal clauses
+        let result = rules[1].evaluate(&context).await;
+        assert_eq!(result.status, RuleStatus::Pass);
+    }
+}
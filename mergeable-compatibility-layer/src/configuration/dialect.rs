@@ -0,0 +1,437 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use snafu::Snafu;
+
+/// `.` without the `s` flag, JavaScript knows more line terminators than `\n`.
+const NOT_LINE_TERMINATOR: &str = "[^\\n\\r\\x{2028}\\x{2029}]";
+
+/// `\b` and `\B` of the `regex` crate only know ASCII when its unicode support is disabled.
+const ASCII_WORD_BOUNDARY: &str = "(?-u:\\b)";
+const ASCII_NOT_WORD_BOUNDARY: &str = "(?-u:\\B)";
+
+/// `\b` and `\B` by lookarounds, `fancy-regex` has no way to disable its unicode support.
+const LOOKAROUND_WORD_BOUNDARY: &str =
+    "(?:(?<=[0-9A-Za-z_])(?![0-9A-Za-z_])|(?<![0-9A-Za-z_])(?=[0-9A-Za-z_]))";
+const LOOKAROUND_NOT_WORD_BOUNDARY: &str =
+    "(?:(?<=[0-9A-Za-z_])(?=[0-9A-Za-z_])|(?<![0-9A-Za-z_])(?![0-9A-Za-z_]))";
+
+/// Properties of strings of the `v` flag, which match sequences of characters.
+const PROPERTIES_OF_STRINGS: [&str; 7] = [
+    "Basic_Emoji",
+    "Emoji_Keycap_Sequence",
+    "RGI_Emoji",
+    "RGI_Emoji_Flag_Sequence",
+    "RGI_Emoji_Modifier_Sequence",
+    "RGI_Emoji_Tag_Sequence",
+    "RGI_Emoji_ZWJ_Sequence",
+];
+
+/// The flags of a JavaScript regex which change the meaning of its syntax.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Syntax {
+    /// `u`, property escapes like `\p{Lu}` instead of a literal `p`.
+    pub(crate) unicode: bool,
+    /// `v`, the `u` flag with nested classes and set operations within classes.
+    pub(crate) unicode_sets: bool,
+    /// `s`, `.` matches line terminators as well.
+    pub(crate) dot_all: bool,
+}
+
+/// The regex engine a translated regex is written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Engine {
+    /// The `regex` crate, which matches in linear time.
+    Regex,
+    /// `fancy-regex`, which backtracks for lookarounds and backreferences and leaves everything
+    /// else to the `regex` crate.
+    FancyRegex,
+}
+
+/// A JavaScript regex in the syntax of the engine which can match it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Translation {
+    pub(crate) pattern: String,
+    pub(crate) engine: Engine,
+}
+
+/// Translates a JavaScript regex, as mergeable configurations were written for, into the syntax
+/// of the `regex` crate with the same meaning.
+///
+/// `\d`, `\w` and `\b` only know ASCII in JavaScript, literal braces and brackets don't need to
+/// be escaped, `[^]` matches anything and `.` no line terminator. Lookarounds and backreferences
+/// need a backtracking engine, a regex using them is translated for `fancy-regex` instead.
+/// Strings within classes have no equivalent in either and are rejected.
+pub(crate) fn translate(source: &str, syntax: Syntax) -> Result<Translation, DialectError> {
+    let mut backtracking = false;
+    let pattern = translate_for(source, syntax, Engine::Regex, &mut backtracking)?;
+    if !backtracking {
+        return Ok(Translation {
+            pattern,
+            engine: Engine::Regex,
+        });
+    }
+    // translated again, as the word boundaries differ
+    let pattern = translate_for(source, syntax, Engine::FancyRegex, &mut backtracking)?;
+    Ok(Translation {
+        pattern,
+        engine: Engine::FancyRegex,
+    })
+}
+
+/// Translates the regex for the engine, noting whether it needs a backtracking one.
+fn translate_for(
+    source: &str,
+    syntax: Syntax,
+    engine: Engine,
+    backtracking: &mut bool,
+) -> Result<String, DialectError> {
+    let mut chars = source.char_indices().peekable();
+    let mut translated = String::with_capacity(source.len());
+    // classes only nest with the `v` flag
+    let mut depth = 0_usize;
+    while let Some((position, char)) = chars.next() {
+        let in_class = depth > 0;
+        match char {
+            '\\' => escape(
+                &mut chars,
+                &mut translated,
+                position,
+                in_class,
+                syntax,
+                engine,
+                backtracking,
+            )?,
+            '[' if in_class && !syntax.unicode_sets => translated.push_str("\\["),
+            '[' => {
+                // both work within the nested classes of the `v` flag as well
+                if next_is(&mut chars, ']') {
+                    translated.push_str("[^\\x00-\\x{10FFFF}]");
+                } else if next_is(&mut chars, '^') {
+                    if next_is(&mut chars, ']') {
+                        translated.push_str("[\\x00-\\x{10FFFF}]");
+                    } else {
+                        translated.push_str("[^");
+                        depth += 1;
+                    }
+                } else {
+                    translated.push('[');
+                    depth += 1;
+                }
+            }
+            ']' if in_class => {
+                translated.push(']');
+                depth -= 1;
+            }
+            // the intersection and subtraction of the `v` flag mean the same in both dialects
+            '&' if in_class && syntax.unicode_sets && next_is(&mut chars, '&') => {
+                translated.push_str("&&");
+            }
+            '-' if in_class && syntax.unicode_sets && next_is(&mut chars, '-') => {
+                translated.push_str("--");
+            }
+            // set operations of the `regex` crate, which are literals in JavaScript
+            '&' | '~' if in_class => {
+                translated.push('\\');
+                translated.push(char);
+            }
+            '-' if in_class && chars.peek().is_some_and(|(_, next)| *next == '-') => {
+                translated.push_str("\\-");
+            }
+            '(' if !in_class && next_is(&mut chars, '?') => {
+                translated.push_str("(?");
+                *backtracking |= lookaround(&mut chars, &mut translated);
+            }
+            '{' if !in_class => match quantifier(source, position) {
+                Some(quantifier) => {
+                    translated.push_str(quantifier);
+                    for _ in 1..quantifier.len() {
+                        chars.next();
+                    }
+                }
+                None => translated.push_str("\\{"),
+            },
+            '}' if !in_class => translated.push_str("\\}"),
+            '.' if !in_class && syntax.dot_all => translated.push_str("(?s:.)"),
+            '.' if !in_class => translated.push_str(NOT_LINE_TERMINATOR),
+            _ => translated.push(char),
+        }
+    }
+    Ok(translated)
+}
+
+fn escape(
+    chars: &mut Peekable<CharIndices<'_>>,
+    translated: &mut String,
+    position: usize,
+    in_class: bool,
+    syntax: Syntax,
+    engine: Engine,
+    backtracking: &mut bool,
+) -> Result<(), DialectError> {
+    let Some((_, escaped)) = chars.next() else {
+        // a trailing backslash is invalid in both dialects
+        translated.push('\\');
+        return Ok(());
+    };
+    let ascii = match (escaped, in_class) {
+        ('d', false) => Some("[0-9]"),
+        ('D', false) => Some("[^0-9]"),
+        ('w', false) => Some("[0-9A-Za-z_]"),
+        ('W', false) => Some("[^0-9A-Za-z_]"),
+        ('b', false) if engine == Engine::Regex => Some(ASCII_WORD_BOUNDARY),
+        ('B', false) if engine == Engine::Regex => Some(ASCII_NOT_WORD_BOUNDARY),
+        ('b', false) => Some(LOOKAROUND_WORD_BOUNDARY),
+        ('B', false) => Some(LOOKAROUND_NOT_WORD_BOUNDARY),
+        ('d', true) => Some("[:digit:]"),
+        ('D', true) => Some("[:^digit:]"),
+        ('w', true) => Some("[:word:]"),
+        ('W', true) => Some("[:^word:]"),
+        // a backspace within a class
+        ('b', true) => Some("\\x08"),
+        ('0', _) => Some("\\x00"),
+        _ => None,
+    };
+    if let Some(ascii) = ascii {
+        translated.push_str(ascii);
+        return Ok(());
+    }
+    match escaped {
+        // backreferences, which both engines write the same
+        '1'..='9' if !in_class => {
+            *backtracking = true;
+            translated.push('\\');
+            translated.push(escaped);
+            Ok(())
+        }
+        'k' if chars.peek().is_some_and(|(_, next)| *next == '<') => {
+            *backtracking = true;
+            translated.push_str("\\k");
+            Ok(())
+        }
+        'p' | 'P' if syntax.unicode || syntax.unicode_sets => {
+            property(chars, translated, position, escaped, syntax)
+        }
+        // without the `u` flag, an escaped letter is the letter itself
+        'p' | 'P' => {
+            translated.push(escaped);
+            Ok(())
+        }
+        'q' if in_class && syntax.unicode_sets => UnsupportedSnafu {
+            construct: "a string within a class",
+            position,
+        }
+        .fail(),
+        'c' if chars
+            .peek()
+            .is_some_and(|(_, next)| next.is_ascii_alphabetic()) =>
+        {
+            let (_, letter) = chars.next().expect("peeked");
+            translated.push_str(&format!("\\x{:02X}", letter as u8 % 32));
+            Ok(())
+        }
+        _ => {
+            translated.push('\\');
+            translated.push(escaped);
+            Ok(())
+        }
+    }
+}
+
+/// A property escape like `\p{Script=Greek}` after its `\p`, the `regex` crate knows the same
+/// properties of characters.
+fn property(
+    chars: &mut Peekable<CharIndices<'_>>,
+    translated: &mut String,
+    position: usize,
+    escape: char,
+    syntax: Syntax,
+) -> Result<(), DialectError> {
+    if !next_is(chars, '{') {
+        return UnsupportedSnafu {
+            construct: "a property escape without braces",
+            position,
+        }
+        .fail();
+    }
+    let mut name = String::new();
+    loop {
+        match chars.next() {
+            Some((_, '}')) => break,
+            Some((_, char)) if char.is_ascii_alphanumeric() || "_=".contains(char) => {
+                name.push(char)
+            }
+            _ => {
+                return UnsupportedSnafu {
+                    construct: "an invalid property escape",
+                    position,
+                }
+                .fail()
+            }
+        }
+    }
+    if syntax.unicode_sets && PROPERTIES_OF_STRINGS.contains(&name.as_str()) {
+        return UnsupportedSnafu {
+            construct: "a property of strings",
+            position,
+        }
+        .fail();
+    }
+    translated.push_str(&format!("\\{escape}{{{name}}}"));
+    Ok(())
+}
+
+/// Copies the kind of a group after its `(?`, returning whether it is a lookaround.
+fn lookaround(chars: &mut Peekable<CharIndices<'_>>, translated: &mut String) -> bool {
+    let behind = next_is(chars, '<');
+    if behind {
+        translated.push('<');
+    }
+    match chars.next_if(|(_, char)| matches!(char, '=' | '!')) {
+        Some((_, kind)) => {
+            translated.push(kind);
+            true
+        }
+        // a named group
+        None => false,
+    }
+}
+
+/// The quantifier like `{2}`, `{2,}` or `{2,5}` at the position, JavaScript reads any other
+/// brace literally.
+fn quantifier(source: &str, position: usize) -> Option<&str> {
+    let rest = &source[position..];
+    let end = rest.find('}')?;
+    let (min, max) = match rest[1..end].split_once(',') {
+        Some((min, max)) => (min, Some(max)),
+        None => (&rest[1..end], None),
+    };
+    let digits = |digits: &str| digits.bytes().all(|digit| digit.is_ascii_digit());
+    let valid = !min.is_empty() && digits(min) && max.is_none_or(digits);
+    valid.then(|| &rest[..=end])
+}
+
+fn next_is(chars: &mut Peekable<CharIndices<'_>>, expected: char) -> bool {
+    chars.next_if(|(_, char)| *char == expected).is_some()
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum DialectError {
+    #[snafu(display(
+        "{construct} at offset {position} can't be translated, the regex engine has no \
+         equivalent"
+    ))]
+    Unsupported {
+        construct: &'static str,
+        position: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::dialect::{translate, Engine, Syntax};
+
+    #[test]
+    fn javascript_syntax_is_translated() {
+        let translate = |javascript| translate(javascript, Syntax::default());
+        let cases = [
+            (r"^\d+-\w*\b", r"^[0-9]+-[0-9A-Za-z_]*(?-u:\b)"),
+            (r"[\d\W_-]", r"[[:digit:][:^word:]_-]"),
+            (
+                r"(?<ticket>[A-Z]+-\d{1,5})",
+                r"(?<ticket>[A-Z]+-[0-9]{1,5})",
+            ),
+            (r"{{ title }}", r"\{\{ title \}\}"),
+            (
+                r"[[&&]][^][]",
+                r"[\[\&\&]][\x00-\x{10FFFF}][^\x00-\x{10FFFF}]",
+            ),
+            (r"v\d.\.$", r"v[0-9][^\n\r\x{2028}\x{2029}]\.$"),
+            (r"\p{L}[\P]", r"p\{L\}[P]"),
+            (r"\/\0\cJ", r"\/\x00\x0A"),
+        ];
+        for (javascript, rust) in cases {
+            let translation = translate(javascript).unwrap();
+            assert_eq!(translation.pattern, rust, "{javascript}");
+            assert_eq!(translation.engine, Engine::Regex, "{javascript}");
+            regex::Regex::new(rust).unwrap();
+        }
+    }
+
+    #[test]
+    fn lookarounds_and_backreferences_are_translated_for_a_backtracking_engine() {
+        let translate = |javascript| translate(javascript, Syntax::default()).unwrap();
+        let cases = [
+            (r"^(?!wip)", "fix", "wip: fix"),
+            (r"(?<=feature/)\w+", "feature/x", "fix/x"),
+            (r"(?<![\d.])\d+$", "v10", "1.0"),
+            (r"^(a)\1$", "aa", "ab"),
+            (r"^(?<x>a)\k<x>$", "aa", "ab"),
+            (r"(?<!-)\bfix\b", "a fix", "a -fix"),
+            (r"(?<=x)a\b", "xaé", "xab"),
+        ];
+        for (javascript, matching, different) in cases {
+            let translation = translate(javascript);
+            assert_eq!(translation.engine, Engine::FancyRegex, "{javascript}");
+            let regex = fancy_regex::Regex::new(&translation.pattern).unwrap();
+            assert!(regex.is_match(matching).unwrap(), "{javascript}");
+            assert!(!regex.is_match(different).unwrap(), "{javascript}");
+        }
+    }
+
+    #[test]
+    fn unicode_flags_change_the_syntax() {
+        let unicode = Syntax {
+            unicode: true,
+            ..Default::default()
+        };
+        let unicode_sets = Syntax {
+            unicode_sets: true,
+            dot_all: true,
+            ..Default::default()
+        };
+        let cases = [
+            (
+                r"\p{Lu}\P{Script=Greek}.",
+                unicode,
+                r"\p{Lu}\P{Script=Greek}[^\n\r\x{2028}\x{2029}]",
+            ),
+            (
+                r"[\p{L}--[a-z]]&&.",
+                unicode_sets,
+                r"[\p{L}--[a-z]]&&(?s:.)",
+            ),
+            (r"[[a-z]&&[^aeiou]]", unicode_sets, r"[[a-z]&&[^aeiou]]"),
+        ];
+        for (javascript, syntax, rust) in cases {
+            assert_eq!(
+                translate(javascript, syntax).unwrap().pattern,
+                rust,
+                "{javascript}"
+            );
+            regex::Regex::new(rust).unwrap();
+        }
+
+        for (javascript, syntax, construct) in [
+            (
+                r"\pL",
+                unicode,
+                "a property escape without braces at offset 0",
+            ),
+            (
+                r"a\p{RGI_Emoji}",
+                unicode_sets,
+                "a property of strings at offset 1",
+            ),
+            (
+                r"[\q{abc}]",
+                unicode_sets,
+                "a string within a class at offset 1",
+            ),
+        ] {
+            let err = translate(javascript, syntax).unwrap_err().to_string();
+            assert!(err.starts_with(construct), "{err}");
+        }
+    }
+}
@@ -1,8 +1,11 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::configuration::basics::{BooleanClause, ChainedAndOrIncludeExcludeClause};
+use crate::configuration::basics::ChainedAndOrIncludeExcludeClause;
+use crate::configuration::payload::{self, PayloadField, PayloadSchemaError};
+use crate::evaluation::Visibility;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "do")]
 #[serde(deny_unknown_fields)]
 pub enum Filter {
@@ -23,64 +26,115 @@ pub enum Filter {
     Not(FilterNot),
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+impl Filter {
+    /// The name of the filter as used by the `do` key of the configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Filter::Author(_) => "author",
+            Filter::Repository(_) => "repository",
+            Filter::Payload(_) => "payload",
+            Filter::And(_) => "and",
+            Filter::Or(_) => "or",
+            Filter::Not(_) => "not",
+        }
+    }
+
+    /// The events the filter can be evaluated for, none for `and`, `or` and `not`, which
+    /// support what their filters support.
+    pub fn supported_events(&self) -> &'static [&'static str] {
+        match self {
+            Filter::Author(_) | Filter::Repository(_) => {
+                &["pull_request.*", "pull_request_review.*"]
+            }
+            Filter::Payload(_) => &["pull_request.*", "pull_request_review.*", "issues.*"],
+            Filter::And(_) | Filter::Or(_) | Filter::Not(_) => &[],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct FilterAnd(FilterStack);
+pub struct FilterAnd(pub(crate) FilterStack);
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct FilterOr(FilterStack);
+pub struct FilterOr(pub(crate) FilterStack);
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct FilterNot(FilterStack);
+pub struct FilterNot(pub(crate) FilterStack);
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-struct FilterStack {
-    filter: Vec<Filter>,
+pub(crate) struct FilterStack {
+    pub(crate) filter: Vec<Filter>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheAuthor {
     #[serde(flatten)]
-    filter: ChainedAndOrIncludeExcludeClause,
+    pub(crate) filter: ChainedAndOrIncludeExcludeClause,
     #[serde(rename = "team")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    team: Option<String>,
+    pub(crate) team: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheRepository {
     #[serde(rename = "visibility")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    visibility: Option<String>,
+    pub(crate) visibility: Option<Visibility>,
     #[serde(rename = "name")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<ChainedAndOrIncludeExcludeClause>,
+    pub(crate) name: Option<ChainedAndOrIncludeExcludeClause>,
     #[serde(rename = "topics")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    topics: Option<ChainedAndOrIncludeExcludeClause>,
+    pub(crate) topics: Option<ChainedAndOrIncludeExcludeClause>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "UncheckedPayload")]
 pub struct ThePayload {
-    // TODO proper handling
     #[serde(skip_serializing_if = "Option::is_none")]
-    pull_request: Option<serde_yaml::Value>,
+    pub(crate) pull_request: Option<PayloadField>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    review: Option<serde_yaml::Value>,
+    pub(crate) review: Option<PayloadField>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    sender: Option<serde_yaml::Value>,
+    pub(crate) sender: Option<PayloadField>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-#[serde(untagged)]
-pub enum X {
-    #[serde(rename = "boolean")]
-    Boolean(BooleanClause),
+struct UncheckedPayload {
+    pull_request: Option<PayloadField>,
+    review: Option<PayloadField>,
+    sender: Option<PayloadField>,
+}
+
+impl TryFrom<UncheckedPayload> for ThePayload {
+    type Error = PayloadSchemaError;
+
+    fn try_from(payload: UncheckedPayload) -> Result<Self, Self::Error> {
+        let fields = [
+            (
+                "pull_request",
+                &payload.pull_request,
+                &payload::PULL_REQUEST,
+            ),
+            ("review", &payload.review, &payload::REVIEW),
+            ("sender", &payload.sender, &payload::SENDER),
+        ];
+        for (path, field, schema) in fields {
+            if let Some(field) = field {
+                field.validate(path, schema)?;
+            }
+        }
+        Ok(Self {
+            pull_request: payload.pull_request,
+            review: payload.review,
+            sender: payload.sender,
+        })
+    }
 }
@@ -0,0 +1,440 @@
+use std::fmt;
+
+use super::actions::Action;
+use super::basics::{ChainedAndOrIncludeExcludeClause, ChainedAndOrIncludeExcludeClauseBeginsEnds};
+use super::compiled::Compiled;
+use super::filter::{Filter, TheRepository};
+use super::options::{Max, Min, MustExclude, MustInclude};
+use super::payload::{PayloadCheck, PayloadField};
+use super::validate::{
+    ChangesetChain, DescriptionChain, HoursBetween, LabelChain, MilestoneChain, TimeConstraint,
+    Validator,
+};
+use super::{Configuration, Rule};
+use crate::evaluation::rule::event_matches;
+use crate::evaluation::RuleStatus;
+
+/// A rule which most likely doesn't do what was intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// Where in the configuration, like `mergeable[0].validate[1].must_exclude`.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl Configuration {
+    /// Finds rules which can never pass or never run, including the ones with a regex or glob
+    /// which can't be compiled and always report an error.
+    pub fn lint(&self) -> Vec<Warning> {
+        let mut lint = Lint::default();
+        let mut compiled = Compiled::default();
+        for (position, rule) in self.mergeable.iter().enumerate() {
+            let location = format!("mergeable[{position}]");
+            for err in compiled.rule(&location, rule) {
+                lint.warn(err.location(), err.reason());
+            }
+            lint.rule(&location, rule);
+        }
+        lint.warnings
+    }
+}
+
+#[derive(Default)]
+struct Lint {
+    warnings: Vec<Warning>,
+}
+
+impl Lint {
+    fn warn(&mut self, location: &str, message: impl Into<String>) {
+        self.warnings.push(Warning {
+            location: location.to_owned(),
+            message: message.into(),
+        });
+    }
+
+    fn rule(&mut self, location: &str, rule: &Rule) {
+        if rule.events().next().is_none() {
+            self.warn(location, "the rule has no 'when', so it never runs");
+        }
+        let events: Vec<_> = rule.events().collect();
+        for (position, filter) in rule.filter.iter().flatten().enumerate() {
+            self.filter(&format!("{location}.filter[{position}]"), filter, &events);
+        }
+        for (position, validator) in rule.validate.iter().flatten().enumerate() {
+            self.validator(
+                &format!("{location}.validate[{position}]"),
+                validator,
+                &events,
+            );
+        }
+        let outcomes = [
+            ("pass", RuleStatus::Pass),
+            ("fail", RuleStatus::Fail),
+            ("error", RuleStatus::Error),
+        ];
+        for (outcome, status) in outcomes {
+            for (position, action) in rule.actions(status).into_iter().enumerate() {
+                let location = format!("{location}.{outcome}[{position}]");
+                self.unsupported_events(
+                    &location,
+                    action.name(),
+                    action.supported_events(),
+                    &events,
+                );
+                // the comment or check run of an unnamed rule is found by the rule's position
+                if rule.name.is_none() && matches!(action, Action::Comment(_) | Action::Checks(_)) {
+                    self.warn(
+                        &location,
+                        format!(
+                            "'{}' of a rule without a 'name' is attached to another rule once the \
+                             rules are reordered, name the rule",
+                            action.name()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Warns about the events which the validator, filter or action can't handle.
+    fn unsupported_events(
+        &mut self,
+        location: &str,
+        name: &str,
+        supported: &[&str],
+        events: &[&str],
+    ) {
+        if supported.is_empty() {
+            return;
+        }
+        for event in events {
+            if !supported
+                .iter()
+                .any(|supported| event_matches(supported, event))
+            {
+                self.warn(
+                    location,
+                    format!(
+                        "'{name}' doesn't support the event '{event}', only {}",
+                        supported.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn filter(&mut self, location: &str, filter: &Filter, events: &[&str]) {
+        self.unsupported_events(location, filter.name(), filter.supported_events(), events);
+        match filter {
+            Filter::Author(author) => self.chain(location, &author.filter),
+            Filter::Repository(repository) => self.repository(location, repository),
+            Filter::Payload(payload) => {
+                for (name, field) in [
+                    ("pull_request", &payload.pull_request),
+                    ("review", &payload.review),
+                    ("sender", &payload.sender),
+                ] {
+                    if let Some(field) = field {
+                        self.payload(&format!("{location}.{name}"), field);
+                    }
+                }
+            }
+            Filter::And(and) => self.filters(location, &and.0.filter, events),
+            Filter::Or(or) => self.filters(location, &or.0.filter, events),
+            Filter::Not(not) => self.filters(location, &not.0.filter, events),
+        }
+    }
+
+    fn filters(&mut self, location: &str, filters: &[Filter], events: &[&str]) {
+        for (position, filter) in filters.iter().enumerate() {
+            self.filter(&format!("{location}.filter[{position}]"), filter, events);
+        }
+    }
+
+    fn repository(&mut self, location: &str, repository: &TheRepository) {
+        if let Some(name) = &repository.name {
+            self.chain(&format!("{location}.name"), name);
+        }
+        if let Some(topics) = &repository.topics {
+            self.chain(&format!("{location}.topics"), topics);
+        }
+    }
+
+    fn payload(&mut self, location: &str, field: &PayloadField) {
+        match field {
+            PayloadField::Nested(fields) => {
+                for (name, field) in fields {
+                    self.payload(&format!("{location}.{name}"), field);
+                }
+            }
+            PayloadField::Check(check) => self.payload_check(location, check),
+        }
+    }
+
+    fn payload_check(&mut self, location: &str, check: &PayloadCheck) {
+        let (Some(include), Some(exclude)) = (&check.include, &check.exclude) else {
+            return;
+        };
+        if include.key != exclude.key {
+            return;
+        }
+        let flags = (
+            include.regex_flag.clone().unwrap_or_default(),
+            exclude.regex_flag.clone().unwrap_or_default(),
+        );
+        if flags.0 != flags.1 {
+            return;
+        }
+        for pattern in include.regex.as_slice() {
+            if exclude.regex.as_slice().contains(pattern) {
+                self.contradiction(location, pattern.as_str());
+            }
+        }
+    }
+
+    fn validator(&mut self, location: &str, validator: &Validator, events: &[&str]) {
+        self.unsupported_events(
+            location,
+            validator.name(),
+            validator.supported_events(),
+            events,
+        );
+        match validator {
+            Validator::Assignee(assignee) => {
+                self.min_max(location, assignee.min.as_ref(), assignee.max.as_ref());
+            }
+            Validator::Author(author) => self.chain(location, &author.author),
+            Validator::BaseRef(base) => {
+                self.include_exclude(location, base.include.as_ref(), base.exclude.as_ref());
+            }
+            Validator::ChangeSet(changeset) => self.chain(location, &changeset.changeset),
+            Validator::Contents(contents) => self.include_exclude(
+                location,
+                contents.content.include.as_ref(),
+                contents.content.exclude.as_ref(),
+            ),
+            Validator::Description(description) => {
+                if let Some(chain) = &description.description {
+                    self.chain(location, chain);
+                }
+            }
+            Validator::HeadRef(head) => {
+                self.include_exclude(location, head.include.as_ref(), head.exclude.as_ref());
+            }
+            Validator::Label(label) => self.chain(location, &label.label),
+            Validator::Milestone(milestone) => self.chain(location, &milestone.milestone),
+            Validator::Stale(stale) => {
+                if let Some(constraint) = &stale.time_constraint {
+                    self.time_constraint(&format!("{location}.time_constraint"), constraint);
+                }
+            }
+            Validator::Title(title) => self.chain(location, &title.title),
+            Validator::And(and) => self.validators(location, &and.0.validate, events),
+            Validator::Or(or) => self.validators(location, &or.0.validate, events),
+            Validator::Not(not) => self.validators(location, &not.0.validate, events),
+            Validator::Age(_)
+            | Validator::Approvals(_)
+            | Validator::Commit(_)
+            | Validator::Dependent(_)
+            | Validator::Project(_)
+            | Validator::Size(_) => {}
+        }
+    }
+
+    fn validators(&mut self, location: &str, validators: &[Validator], events: &[&str]) {
+        for (position, validator) in validators.iter().enumerate() {
+            self.validator(
+                &format!("{location}.validate[{position}]"),
+                validator,
+                events,
+            );
+        }
+    }
+
+    fn chain(&mut self, location: &str, chain: &impl Chain) {
+        let clauses = chain.clauses();
+        self.include_exclude(location, clauses.include, clauses.exclude);
+        self.min_max(location, clauses.min, clauses.max);
+        for (operator, chains) in chain.operands() {
+            for (position, chain) in chains.iter().enumerate() {
+                self.chain(&format!("{location}.{operator}[{position}]"), chain);
+            }
+        }
+    }
+
+    fn include_exclude(
+        &mut self,
+        location: &str,
+        include: Option<&MustInclude>,
+        exclude: Option<&MustExclude>,
+    ) {
+        let (Some(include), Some(exclude)) = (include, exclude) else {
+            return;
+        };
+        let (included, include_flags) = include.0.patterns();
+        let (excluded, exclude_flags) = exclude.0.patterns();
+        if include_flags != exclude_flags {
+            return;
+        }
+        for pattern in included {
+            if excluded.contains(pattern) {
+                self.contradiction(location, pattern.as_str());
+            }
+        }
+    }
+
+    fn contradiction(&mut self, location: &str, pattern: &str) {
+        self.warn(
+            location,
+            format!("'must_include' and 'must_exclude' both use the regex '{pattern}', so it can never pass"),
+        );
+    }
+
+    fn min_max(&mut self, location: &str, min: Option<&Min>, max: Option<&Max>) {
+        let (Some(min), Some(max)) = (min, max) else {
+            return;
+        };
+        if min.0.count > max.0.count {
+            self.warn(
+                location,
+                format!(
+                    "'min.count' of {} is greater than 'max.count' of {}, so it can never pass",
+                    min.0.count, max.0.count
+                ),
+            );
+        }
+    }
+
+    fn time_constraint(&mut self, location: &str, constraint: &TimeConstraint) {
+        let Some(HoursBetween { from, to }) = constraint.hours_between else {
+            return;
+        };
+        if from > to {
+            self.warn(
+                location,
+                format!("'hours_between' from {from} to {to} is empty, so it never runs"),
+            );
+        }
+    }
+}
+
+/// The clauses of a condition which are checked together.
+#[derive(Default)]
+pub(crate) struct Clauses<'a> {
+    pub(crate) include: Option<&'a MustInclude>,
+    pub(crate) exclude: Option<&'a MustExclude>,
+    pub(crate) min: Option<&'a Min>,
+    pub(crate) max: Option<&'a Max>,
+}
+
+/// A condition with nested `and` and `or` conditions of the same shape.
+pub(crate) trait Chain: Sized {
+    fn clauses(&self) -> Clauses<'_>;
+
+    fn operands(&self) -> [(&'static str, &[Self]); 2];
+}
+
+/// Implements [`Chain`] for a condition with `must_include`, `must_exclude`, `and` and `or`, and
+/// the listed count clauses.
+macro_rules! impl_chain {
+    ($($chain:ty $({ $($count:ident),+ })?),+ $(,)?) => {
+        $(
+            impl Chain for $chain {
+                fn clauses(&self) -> Clauses<'_> {
+                    #[allow(unused_mut)]
+                    let mut clauses = Clauses {
+                        include: self.include.as_ref(),
+                        exclude: self.exclude.as_ref(),
+                        ..Default::default()
+                    };
+                    $($(clauses.$count = self.$count.as_ref();)+)?
+                    clauses
+                }
+
+                fn operands(&self) -> [(&'static str, &[Self]); 2] {
+                    [
+                        ("and", self.and.as_deref().unwrap_or_default()),
+                        ("or", self.or.as_deref().unwrap_or_default()),
+                    ]
+                }
+            }
+        )+
+    };
+}
+
+impl_chain!(
+    ChainedAndOrIncludeExcludeClause,
+    ChainedAndOrIncludeExcludeClauseBeginsEnds,
+    ChangesetChain { min, max },
+    DescriptionChain,
+    LabelChain,
+    MilestoneChain,
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn contradictions_and_unsupported_events_are_reported() {
+        let config = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: pull_request.opened, issues.opened
+                filter:
+                  - do: repository
+                    visibility: public
+                validate:
+                  - do: title
+                    or:
+                      - must_include:
+                          regex: wip
+                        must_exclude:
+                          regex: [dnm, wip]
+                  - do: assignee
+                    min:
+                      count: 3
+                    max:
+                      count: 1
+                  - do: and
+                    validate:
+                      - do: headRef
+                        must_include:
+                          regex: '^feature/'
+                pass:
+                  - do: request_review
+                    reviewers: octocat
+                fail:
+                  - do: comment
+                    payload:
+                      body: Not ready yet
+              - name: stale
+                validate:
+                  - do: stale
+                    time_constraint:
+                      hours_between: ['17', '9']
+        "})
+        .unwrap();
+
+        let warnings: Vec<_> = config.lint().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            [
+                "mergeable[0].filter[0]: 'repository' doesn't support the event 'issues.opened', only pull_request.*, pull_request_review.*",
+                "mergeable[0].validate[0].or[0]: 'must_include' and 'must_exclude' both use the regex 'wip', so it can never pass",
+                "mergeable[0].validate[1]: 'min.count' of 3 is greater than 'max.count' of 1, so it can never pass",
+                "mergeable[0].validate[2].validate[0]: 'headRef' doesn't support the event 'issues.opened', only pull_request.*, pull_request_review.*",
+                "mergeable[0].pass[0]: 'request_review' doesn't support the event 'issues.opened', only pull_request.*",
+                "mergeable[0].fail[0]: 'comment' of a rule without a 'name' is attached to another rule once the rules are reordered, name the rule",
+                "mergeable[1]: the rule has no 'when', so it never runs",
+                "mergeable[1].validate[0].time_constraint: 'hours_between' from 17 to 9 is empty, so it never runs",
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::configuration::basics::{
@@ -8,49 +9,49 @@ use crate::configuration::basics::{
 
 /// Supported Validators:
 ///   'payload'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct CommonBoolean(BooleanClause);
+pub struct CommonBoolean(pub(crate) BooleanClause);
 
 /// Supported Validators:
 ///   'changeset', 'content', 'description', 'label', 'milestone', 'title'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct BeginsWith(MatchClause);
+pub struct BeginsWith(pub(crate) MatchClause);
 
 /// Supported Validators:
 ///   'changeset', 'content', 'description', 'label', 'milestone', 'title'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct EndsWith(MatchClause);
+pub struct EndsWith(pub(crate) MatchClause);
 
 /// Supported Validators:
 ///   'baseRef', 'headRef', 'changeset', 'commit', 'content', 'description', 'label', 'milestone', 'project', 'title'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct MustInclude(RegexClause);
+pub struct MustInclude(pub(crate) RegexClause);
 
 /// Supported Validators:
 ///   'baseRef', 'headRef', 'changeset', 'content', 'description', 'label', 'milestone', 'title'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct MustExclude(RegexClause);
+pub struct MustExclude(pub(crate) RegexClause);
 
 /// Supported Validators:
 ///   'changeset', 'description', 'label', 'milestone', 'title'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct NoEmpty {
     #[serde(rename = "enabled")]
-    enabled: bool,
+    pub(crate) enabled: bool,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
 /// Supported Validators:
 ///   'approvals'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Required {
     #[serde(rename = "reviewers")]
@@ -72,18 +73,18 @@ pub struct Required {
 
 /// Supported Validators:
 ///   'approvals', 'assignee', 'changeset', 'label', 'size'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct Min(CountClause);
+pub struct Min(pub(crate) CountClause);
 
 /// Supported Validators:
 ///   'approvals', 'assignee', 'changeset', 'label'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct Max(CountClause);
+pub struct Max(pub(crate) CountClause);
 
 /// Supported Validators:
 ///   'commit', 'description', 'headRef', 'label', 'milestone', 'title'
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct Jira(RegexClause);
+pub struct Jira(pub(crate) RegexClause);
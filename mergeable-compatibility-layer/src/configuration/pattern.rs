@@ -0,0 +1,265 @@
+use std::fmt::{self, Display, Formatter};
+
+use regex::RegexBuilder;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+use super::dialect::{self, DialectError, Engine, Syntax};
+
+/// A JavaScript regex as written in the configuration.
+///
+/// Compiled with the flags of its clause into [`Regexes`] when the configuration is compiled,
+/// translating it into the syntax of the `regex` crate, or of `fancy-regex` if it needs lookarounds
+/// or backreferences.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Pattern(String);
+
+impl Pattern {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn compile(&self, flags: &RegexFlags) -> Result<Regex, PatternError> {
+        let translation = dialect::translate(&self.0, flags.syntax())
+            .context(UntranslatableSnafu { pattern: &self.0 })?;
+        // a sticky regex is tested from the start only
+        let anchored = if flags.flags().any(|flag| flag == 'y') {
+            format!("\\A(?:{})", translation.pattern)
+        } else {
+            translation.pattern
+        };
+        if translation.engine == Engine::FancyRegex {
+            // `fancy-regex` only takes the case insensitivity from its builder
+            let inline = flags.inline();
+            let inlined = if inline.is_empty() {
+                anchored
+            } else {
+                format!("(?{inline}){anchored}")
+            };
+            return fancy_regex::Regex::new(&inlined)
+                .map(Regex::Backtracking)
+                .map_err(Box::new)
+                .context(InvalidBacktrackingRegexSnafu { pattern: &self.0 });
+        }
+        let mut builder = RegexBuilder::new(&anchored);
+        for flag in flags.flags() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                // unicode is always enabled, global matching and indices have no meaning for a
+                // predicate
+                _ => &mut builder,
+            };
+        }
+        builder
+            .build()
+            .map(Regex::Fast)
+            .context(InvalidRegexSnafu { pattern: &self.0 })
+    }
+}
+
+/// A compiled [`Pattern`], matched in linear time unless it needs backtracking.
+#[derive(Debug, Clone)]
+pub enum Regex {
+    Fast(regex::Regex),
+    Backtracking(fancy_regex::Regex),
+}
+
+impl Regex {
+    /// Whether the regex matches the value, a regex which exceeds the backtrack limit of
+    /// `fancy-regex` doesn't match.
+    pub fn is_match(&self, value: &str) -> bool {
+        match self {
+            Self::Fast(regex) => regex.is_match(value),
+            Self::Backtracking(regex) => regex.is_match(value).unwrap_or(false),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Fast(regex) => regex.as_str(),
+            Self::Backtracking(regex) => regex.as_str(),
+        }
+    }
+}
+
+impl Display for Regex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Pattern {
+    fn from(source: &str) -> Self {
+        Self(source.to_owned())
+    }
+}
+
+impl JsonSchema for Pattern {
+    fn schema_name() -> String {
+        "Pattern".to_owned()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// The patterns of a clause, compiled with its flags.
+#[derive(Debug, Clone, Default)]
+pub struct Regexes(Vec<Regex>);
+
+impl Regexes {
+    pub fn compile(patterns: &[Pattern], flags: &RegexFlags) -> Result<Self, PatternError> {
+        flags.check()?;
+        patterns
+            .iter()
+            .map(|pattern| pattern.compile(flags))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Regex> {
+        self.0.iter()
+    }
+
+    /// The compiled regexes with the flags inline, e.g. `(?i)^wip`.
+    pub fn inline<'a>(&'a self, flags: &'a RegexFlags) -> impl Iterator<Item = String> + 'a {
+        let inline = flags.inline();
+        self.0.iter().map(move |regex| match regex {
+            Regex::Fast(regex) if !inline.is_empty() => format!("(?{inline}){}", regex.as_str()),
+            // the flags of a backtracking regex are inline already
+            _ => regex.as_str().to_owned(),
+        })
+    }
+}
+
+/// The flags of a regex like `im`, mergeable matches case-insensitive unless they are `none`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct RegexFlags(String);
+
+impl RegexFlags {
+    fn check(&self) -> Result<(), PatternError> {
+        match self.flags().find(|flag| !"imsugydv".contains(*flag)) {
+            Some(flag) => InvalidRegexFlagSnafu { flag }.fail(),
+            None => Ok(()),
+        }
+    }
+
+    fn flags(&self) -> impl Iterator<Item = char> + '_ {
+        let flags = if self.0 == "none" { "" } else { &self.0 };
+        flags.chars()
+    }
+
+    /// The flags which both regex crates take inline.
+    fn inline(&self) -> String {
+        let mut inline = String::new();
+        for flag in self.flags() {
+            // the regex crates reject repeated flags, which JavaScript ignores
+            if "ims".contains(flag) && !inline.contains(flag) {
+                inline.push(flag);
+            }
+        }
+        inline
+    }
+
+    fn syntax(&self) -> Syntax {
+        let mut syntax = Syntax::default();
+        for flag in self.flags() {
+            match flag {
+                'u' => syntax.unicode = true,
+                'v' => syntax.unicode_sets = true,
+                's' => syntax.dot_all = true,
+                _ => {}
+            }
+        }
+        syntax
+    }
+}
+
+impl Default for RegexFlags {
+    fn default() -> Self {
+        Self("i".to_owned())
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum PatternError {
+    #[snafu(display("Invalid regex '{pattern}': {source}"))]
+    InvalidRegex {
+        pattern: String,
+        source: regex::Error,
+    },
+    #[snafu(display("Invalid regex '{pattern}': {source}"))]
+    InvalidBacktrackingRegex {
+        pattern: String,
+        source: Box<fancy_regex::Error>,
+    },
+    #[snafu(display("Unsupported regex '{pattern}': {source}"))]
+    Untranslatable {
+        pattern: String,
+        source: DialectError,
+    },
+    #[snafu(display(
+        "Unsupported regex flag '{flag}', supported are 'i', 'm', 's', 'u', 'g', 'y', 'd', 'v' \
+         or 'none'"
+    ))]
+    InvalidRegexFlag { flag: char },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::pattern::{Pattern, RegexFlags, Regexes};
+
+    #[test]
+    fn patterns_and_flags_are_checked_when_compiled() {
+        let pattern: Pattern = serde_yaml::from_str("'^wip'").unwrap();
+        let flags: RegexFlags = serde_yaml::from_str("im").unwrap();
+        let none: RegexFlags = serde_yaml::from_str("none").unwrap();
+        // the same pattern in clauses with differing flags
+        assert!(pattern.compile(&flags).unwrap().is_match("WIP: parser"));
+        assert!(!pattern.compile(&none).unwrap().is_match("WIP: parser"));
+        assert_eq!(serde_yaml::to_string(&none).unwrap(), "none\n");
+
+        let pattern: Pattern = serde_yaml::from_str("'(wip'").unwrap();
+        let err = Regexes::compile(&[pattern], &flags).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid regex '(wip'"), "{err}");
+        let pattern = Pattern::from("(?<=feature/)x");
+        assert!(pattern.compile(&flags).unwrap().is_match("FEATURE/X"));
+        assert!(!pattern.compile(&flags).unwrap().is_match("fix/x"));
+        let pattern = Pattern::from("(?<=a+)x");
+        let err = pattern.compile(&flags).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid regex '(?<=a+)x'"),
+            "{err}"
+        );
+        let pattern: Pattern = serde_yaml::from_str("'\\bwip'").unwrap();
+        let sticky: RegexFlags = serde_yaml::from_str("iy").unwrap();
+        assert!(!pattern.compile(&sticky).unwrap().is_match("fix: wip"));
+        let pattern: Pattern = serde_yaml::from_str("'^\\p{Lu}.'").unwrap();
+        let unicode: RegexFlags = serde_yaml::from_str("u").unwrap();
+        assert!(pattern.compile(&unicode).unwrap().is_match("Ä!"));
+        assert!(!pattern.compile(&unicode).unwrap().is_match("Ä\n"));
+        assert!(pattern.compile(&none).unwrap().is_match("p{Lu}!"));
+        let pattern: Pattern = serde_yaml::from_str("'\\pL'").unwrap();
+        let err = pattern.compile(&unicode).unwrap_err();
+        assert!(
+            err.to_string().starts_with(
+                "Unsupported regex '\\pL': a property escape without braces at offset 0"
+            ),
+            "{err}"
+        );
+        let unknown: RegexFlags = serde_yaml::from_str("ix").unwrap();
+        let err = Regexes::compile(&[pattern], &unknown).unwrap_err();
+        assert!(
+            err.to_string().starts_with("Unsupported regex flag 'x'"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_yaml::Value as YamlValue;
+use snafu::Snafu;
+
+use crate::configuration::basics::{BooleanClause, MessageClause, OneOrMany};
+use crate::configuration::pattern::{Pattern, RegexFlags};
+
+/// A field of the webhook payload, either checked directly or descended into.
+///
+/// Deserialized by hand instead of `untagged`, which would hide why a check is invalid.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PayloadField {
+    Check(Box<PayloadCheck>),
+    Nested(BTreeMap<String, PayloadField>),
+}
+
+/// The keys of [`PayloadCheck`], a map with only these is a check instead of nested fields.
+const CHECKS: [&str; 3] = ["must_include", "must_exclude", "boolean"];
+
+impl<'de> Deserialize<'de> for PayloadField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = BTreeMap::<String, YamlValue>::deserialize(deserializer)?;
+        if !fields.is_empty() && fields.keys().all(|key| CHECKS.contains(&key.as_str())) {
+            let fields = fields.into_iter().map(|(key, value)| (key.into(), value));
+            return serde_yaml::from_value(YamlValue::Mapping(fields.collect()))
+                .map(|check| PayloadField::Check(Box::new(check)))
+                .map_err(D::Error::custom);
+        }
+        fields
+            .into_iter()
+            .map(|(name, value)| {
+                let field = serde_yaml::from_value(value)
+                    .map_err(|err| D::Error::custom(format!("{name}: {err}")))?;
+                Ok((name, field))
+            })
+            .collect::<Result<_, _>>()
+            .map(PayloadField::Nested)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadCheck {
+    #[serde(rename = "must_include")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) include: Option<PayloadRegexClause>,
+    #[serde(rename = "must_exclude")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) exclude: Option<PayloadRegexClause>,
+    #[serde(rename = "boolean")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) boolean: Option<BooleanClause>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadRegexClause {
+    #[serde(rename = "regex")]
+    pub(crate) regex: OneOrMany<Pattern>,
+    /// Field of the objects to match against, if the payload field is a list of objects.
+    #[serde(rename = "key")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) key: Option<String>,
+    #[serde(rename = "regex_flag")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) regex_flag: Option<RegexFlags>,
+    #[serde(rename = "message")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) message: Option<MessageClause>,
+}
+
+/// The shape of the parts of a webhook payload which can be filtered on.
+#[derive(Debug)]
+pub enum EventField {
+    Boolean,
+    String,
+    Number,
+    Array(&'static EventField),
+    Object(&'static [(&'static str, EventField)]),
+}
+
+impl EventField {
+    fn field(&self, name: &str) -> Option<&'static EventField> {
+        match self {
+            EventField::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, kind)| kind),
+            _ => None,
+        }
+    }
+}
+
+const USER: EventField = EventField::Object(&[
+    ("login", EventField::String),
+    ("id", EventField::Number),
+    ("node_id", EventField::String),
+    ("type", EventField::String),
+    ("site_admin", EventField::Boolean),
+    ("html_url", EventField::String),
+]);
+
+const LABEL: EventField = EventField::Object(&[
+    ("name", EventField::String),
+    ("color", EventField::String),
+    ("description", EventField::String),
+    ("default", EventField::Boolean),
+]);
+
+const TEAM: EventField = EventField::Object(&[
+    ("name", EventField::String),
+    ("slug", EventField::String),
+    ("description", EventField::String),
+    ("privacy", EventField::String),
+]);
+
+const MILESTONE: EventField = EventField::Object(&[
+    ("number", EventField::Number),
+    ("title", EventField::String),
+    ("description", EventField::String),
+    ("state", EventField::String),
+]);
+
+const BRANCH: EventField = EventField::Object(&[
+    ("label", EventField::String),
+    ("ref", EventField::String),
+    ("sha", EventField::String),
+    ("user", USER),
+]);
+
+pub const PULL_REQUEST: EventField = EventField::Object(&[
+    ("id", EventField::Number),
+    ("node_id", EventField::String),
+    ("number", EventField::Number),
+    ("html_url", EventField::String),
+    ("state", EventField::String),
+    ("locked", EventField::Boolean),
+    ("active_lock_reason", EventField::String),
+    ("title", EventField::String),
+    ("body", EventField::String),
+    ("draft", EventField::Boolean),
+    ("merged", EventField::Boolean),
+    ("mergeable", EventField::Boolean),
+    ("rebaseable", EventField::Boolean),
+    ("mergeable_state", EventField::String),
+    ("maintainer_can_modify", EventField::Boolean),
+    ("author_association", EventField::String),
+    ("comments", EventField::Number),
+    ("review_comments", EventField::Number),
+    ("commits", EventField::Number),
+    ("additions", EventField::Number),
+    ("deletions", EventField::Number),
+    ("changed_files", EventField::Number),
+    ("created_at", EventField::String),
+    ("updated_at", EventField::String),
+    ("closed_at", EventField::String),
+    ("merged_at", EventField::String),
+    ("user", USER),
+    ("assignee", USER),
+    ("assignees", EventField::Array(&USER)),
+    ("requested_reviewers", EventField::Array(&USER)),
+    ("requested_teams", EventField::Array(&TEAM)),
+    ("labels", EventField::Array(&LABEL)),
+    ("milestone", MILESTONE),
+    ("head", BRANCH),
+    ("base", BRANCH),
+]);
+
+pub const REVIEW: EventField = EventField::Object(&[
+    ("id", EventField::Number),
+    ("node_id", EventField::String),
+    ("html_url", EventField::String),
+    ("body", EventField::String),
+    ("state", EventField::String),
+    ("commit_id", EventField::String),
+    ("submitted_at", EventField::String),
+    ("author_association", EventField::String),
+    ("user", USER),
+]);
+
+pub const SENDER: EventField = USER;
+
+impl PayloadField {
+    /// Verifies every referenced field exists in the payload and supports the requested check.
+    pub(crate) fn validate(
+        &self,
+        path: &str,
+        schema: &EventField,
+    ) -> Result<(), PayloadSchemaError> {
+        match self {
+            PayloadField::Nested(fields) => {
+                for (name, field) in fields {
+                    let path = format!("{path}.{name}");
+                    let Some(schema) = schema.field(name) else {
+                        return UnknownFieldSnafu { path }.fail();
+                    };
+                    field.validate(&path, schema)?;
+                }
+                Ok(())
+            }
+            PayloadField::Check(check) => check.validate(path, schema),
+        }
+    }
+}
+
+impl PayloadCheck {
+    fn validate(&self, path: &str, schema: &EventField) -> Result<(), PayloadSchemaError> {
+        if self.boolean.is_some() && !matches!(schema, EventField::Boolean) {
+            return UnsupportedCheckSnafu {
+                path,
+                check: "boolean",
+            }
+            .fail();
+        }
+        for (check, clause) in [
+            ("must_include", &self.include),
+            ("must_exclude", &self.exclude),
+        ] {
+            let Some(clause) = clause else {
+                continue;
+            };
+            match (schema, &clause.key) {
+                (EventField::String | EventField::Number, None) => {}
+                (EventField::Array(EventField::String), None) => {}
+                (EventField::Array(item @ EventField::Object(_)), Some(key)) => {
+                    if !matches!(
+                        item.field(key),
+                        Some(EventField::String | EventField::Number)
+                    ) {
+                        return UnknownKeySnafu { path, key }.fail();
+                    }
+                }
+                (EventField::Array(EventField::Object(_)), None) => {
+                    return MissingKeySnafu { path }.fail();
+                }
+                _ => return UnsupportedCheckSnafu { path, check }.fail(),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum PayloadSchemaError {
+    #[snafu(display("The field '{path}' does not exist in the event payload"))]
+    UnknownField { path: String },
+    #[snafu(display("The field '{path}' does not support '{check}'"))]
+    UnsupportedCheck { path: String, check: &'static str },
+    #[snafu(display("The objects of '{path}' have no text field '{key}'"))]
+    UnknownKey { path: String, key: String },
+    #[snafu(display("The field '{path}' is a list of objects and requires a 'key' to match on"))]
+    MissingKey { path: String },
+}
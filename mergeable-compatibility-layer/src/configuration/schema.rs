@@ -0,0 +1,53 @@
+use schemars::gen::SchemaSettings;
+
+use super::Configuration;
+
+/// The JSON Schema of `.github/mergeable.yml`, for editors to validate and autocomplete it.
+pub fn schema() -> serde_json::Value {
+    let generator = SchemaSettings::draft07().into_generator();
+    let mut schema = serde_json::to_value(generator.into_root_schema_for::<Configuration>())
+        .expect("schemas are plain JSON");
+    if let Some(schema) = schema.as_object_mut() {
+        schema.insert("title".to_owned(), "mergeable configuration".into());
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::schema;
+
+    #[test]
+    fn actions_and_validators_are_tagged_by_do() {
+        let schema = schema();
+        let definitions = &schema["definitions"];
+
+        let actions: Vec<_> = definitions["Action"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|variant| variant["properties"]["do"]["enum"].as_array().unwrap())
+            .map(|name| name.as_str().unwrap())
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                "assign",
+                "checks",
+                "close",
+                "comment",
+                "merge",
+                "labels",
+                "request_review"
+            ]
+        );
+        assert!(definitions["Validator"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|variant| variant["properties"]["do"]["enum"][0] == "stale"));
+        let one_or_many = &definitions["OneOrMany_for_String"]["anyOf"];
+        assert_eq!(one_or_many[0]["type"], "array");
+        assert_eq!(one_or_many[1]["type"], "string");
+    }
+}
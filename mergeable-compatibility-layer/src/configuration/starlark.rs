@@ -0,0 +1,1193 @@
+use std::fmt::Write;
+
+use serde_json::Value as JsonValue;
+
+use super::basics::{
+    ChainedAndOrIncludeExcludeClause, ChainedAndOrIncludeExcludeClauseBeginsEnds, MatchClause,
+    MessageClause, RegexClause,
+};
+use super::compiled::Compiled;
+use super::filter::{Filter, TheRepository};
+use super::lint::Warning;
+use super::options::{BeginsWith, EndsWith, Max, Min, MustExclude, MustInclude, NoEmpty};
+use super::pattern::{Pattern, RegexFlags};
+use super::payload::{PayloadCheck, PayloadField, PayloadRegexClause};
+use super::validate::{
+    DescriptionChain, LabelChain, MilestoneChain, TheAssignee, TheLabel, TheMilestone, Validator,
+};
+use super::{Configuration, Rule};
+use crate::evaluation::RuleStatus;
+
+/// Helpers of the converted rules, which build their results like the evaluation of this crate.
+///
+/// The converted rules evaluate more validators though: `title`, `label`, `description`, `baseRef`
+/// and `headRef` read the event payload in Starlark, while `Validator::evaluate` reports them as
+/// unsupported.
+const PRELUDE: &str = r##"_ICONS = {"pass": "✔️", "fail": "❌", "error": "⚠️"}
+
+def _listens(when, event):
+    for pattern in when:
+        if pattern == event or (pattern.endswith(".*") and event.startswith(pattern[:-1])):
+            return True
+    return False
+
+def _rule(name, payload, filters, validators, actions):
+    result = {"name": name, "status": "pass", "filters": [], "validators": [], "actions": []}
+    for filter in filters:
+        filtered = filter(payload)
+        result["filters"].append(filtered)
+        if filtered["status"] == "fail":
+            result["status"] = "skipped"
+            return result
+        if filtered["status"] == "error":
+            result["status"] = "error"
+            result["actions"] = actions["error"]
+            return result
+    for validator in validators:
+        validated = validator(payload)
+        result["validators"].append(validated)
+        if result["status"] == "error" or validated["status"] == "error":
+            result["status"] = "error"
+        elif result["status"] == "fail" or validated["status"] == "fail":
+            result["status"] = "fail"
+    result["actions"] = actions[result["status"]]
+    return result
+
+def _result(name, failures, passed):
+    if failures:
+        return {"name": name, "status": "fail", "description": "\n".join(failures)}
+    return {"name": name, "status": "pass", "description": passed}
+
+def _unconverted(name):
+    return {"name": name, "status": "error", "description": "'%s' isn't converted to Starlark" % name}
+
+def _all(name, nodes, payload):
+    children = []
+    for node in nodes:
+        child = node(payload)
+        children.append(child)
+        if child["status"] != "pass":
+            return _composite(name, child["status"], children)
+    return _composite(name, "pass", children)
+
+def _any(name, nodes, payload):
+    children = []
+    for node in nodes:
+        child = node(payload)
+        children.append(child)
+        if child["status"] == "pass":
+            return _composite(name, "pass", children)
+    errors = [child for child in children if child["status"] == "error"]
+    return _composite(name, "error" if errors else "fail", children)
+
+def _none(name, nodes, payload):
+    inner = _all(name, nodes, payload)
+    status = {"pass": "fail", "fail": "pass", "error": "error"}[inner["status"]]
+    return _composite(name, status, inner["children"])
+
+def _composite(name, status, children):
+    lines = []
+    for child in children:
+        icon = _ICONS[child["status"]]
+        description = child["description"].replace("\n", "\n  ")
+        if child.get("children"):
+            lines.append("%s %s\n  %s" % (icon, child["name"], description))
+        else:
+            lines.append("%s %s: %s" % (icon, child["name"], description))
+    return {"name": name, "status": status, "description": "\n".join(lines), "children": children}
+
+def _item(payload):
+    return payload.get("pull_request") or payload.get("issue") or {}
+
+def _field(value, path):
+    for name in path:
+        if type(value) != "dict":
+            return None
+        value = value.get(name)
+    return value
+
+def _text(value):
+    if type(value) == "string":
+        return value
+    if type(value) == "bool":
+        return "true" if value else "false"
+    if type(value) in ("int", "float"):
+        return str(value)
+    return None
+
+def _values(value, key):
+    if type(value) == "list":
+        items = [_field(item, [key]) if key else item for item in value]
+    else:
+        items = [value]
+    return [_text(item) for item in items if _text(item) != None]
+
+def _visibility(repository):
+    visibility = repository.get("visibility")
+    if visibility in ("public", "internal"):
+        return visibility
+    if visibility == None and repository.get("private") == False:
+        return "public"
+    return "private"
+
+def _matches_any(regex, values):
+    for value in values:
+        if matches(regex, value):
+            return True
+    return False
+
+def _include(subject, regexes, values, message):
+    return [
+        message or "%s does not include '%s'" % (subject, source)
+        for source, regex in regexes
+        if not _matches_any(regex, values)
+    ]
+
+def _exclude(subject, regexes, values, message):
+    return [
+        message or "%s must exclude '%s'" % (subject, source)
+        for source, regex in regexes
+        if _matches_any(regex, values)
+    ]
+
+def _begins_with(subject, patterns, values, message):
+    return [
+        message or "%s does not begin with '%s'" % (subject, "' or '".join(patterns))
+        for value in values
+        if not [pattern for pattern in patterns if value.startswith(pattern)]
+    ]
+
+def _ends_with(subject, patterns, values, message):
+    return [
+        message or "%s does not end with '%s'" % (subject, "' or '".join(patterns))
+        for value in values
+        if not [pattern for pattern in patterns if value.endswith(pattern)]
+    ]
+
+def _min(subject, count, min, message):
+    if count >= min:
+        return []
+    return [message or "%s count is %d, but at least %d are required" % (subject, count, min)]
+
+def _max(subject, count, max, message):
+    if count <= max:
+        return []
+    return [message or "%s count is %d, but at most %d are allowed" % (subject, count, max)]
+
+def _vars(payload, validator):
+    item = _item(payload)
+    number = item.get("number")
+    return {
+        "author": (item.get("user") or {}).get("login") or "",
+        "title": item.get("title") or "",
+        "number": "" if number == None else str(number),
+        "repository": (payload.get("repository") or {}).get("name") or "",
+        "validator": validator,
+    }
+
+def _refs(payload, side):
+    item = _item(payload)
+    if item.get(side):
+        return [item[side].get("ref") or ""]
+    pull_requests = (payload.get("check_suite") or {}).get("pull_requests") or []
+    return [(pull_request.get(side) or {}).get("ref") or "" for pull_request in pull_requests]
+
+def _or(clauses):
+    for failures in clauses:
+        if not failures:
+            return []
+    return [failure for failures in clauses for failure in failures]
+
+def evaluate(event):
+    results = []
+    for rule in RULES:
+        if _listens(rule["when"], event["name"]):
+            result = rule["evaluate"](event["payload"])
+            emit(result)
+            results.append(result)
+    return results
+"##;
+
+/// A configuration converted to a Starlark script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversion {
+    pub script: String,
+    /// The filters and validators which need more than the event payload, they report an error
+    /// in the script, and the message templates which are kept as written.
+    pub unconverted: Vec<Warning>,
+}
+
+impl Configuration {
+    /// Converts the rules to a Starlark script, whose `evaluate(event)` evaluates the rules
+    /// listening to the event and emits their results, e.g. by
+    /// `event_processor::evaluate_event`.
+    ///
+    /// The event is a dict of its `name`, e.g. `pull_request.opened`, and the webhook `payload`.
+    /// The actions of the outcome are part of a result, to be executed by the caller.
+    pub fn to_starlark(&self) -> Conversion {
+        let mut converter = Converter::default();
+        let mut rules = String::new();
+        for (position, rule) in self.mergeable.iter().enumerate() {
+            let function = converter.rule(position, self.rule_key(rule), rule);
+            let when: Vec<_> = rule.events().map(JsonValue::from).collect();
+            let _ = writeln!(
+                rules,
+                "    {{\"name\": {}, \"when\": {}, \"evaluate\": {function}}},",
+                literal(&JsonValue::from(self.rule_key(rule))),
+                literal(&JsonValue::from(when)),
+            );
+        }
+        let script = format!(
+            "# Converted from a mergeable configuration.\n\
+             \n{PRELUDE}{}\nRULES = [\n{rules}]\n",
+            converter.functions
+        );
+        Conversion {
+            script,
+            unconverted: converter.unconverted,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Converter {
+    functions: String,
+    unconverted: Vec<Warning>,
+    /// The regexes of the rules, compiled like for their evaluation.
+    compiled: Compiled,
+    /// Position of the rule and count of its functions, which make up the function names.
+    rule: usize,
+    count: usize,
+}
+
+impl Converter {
+    /// Adds a function with the parameter and body, returning its name.
+    fn function(&mut self, parameter: &str, body: &str) -> String {
+        self.count += 1;
+        let name = format!("_rule_{}_{}", self.rule + 1, self.count);
+        let _ = write!(self.functions, "\ndef {name}({parameter}):\n{body}");
+        name
+    }
+
+    fn unconverted(&mut self, location: &str, name: &str, reason: &str) -> String {
+        self.unconverted.push(Warning {
+            location: location.to_owned(),
+            message: format!("'{name}' {reason}, it reports an error in Starlark"),
+        });
+        self.function(
+            "payload",
+            &format!("    return _unconverted({})\n", string(name)),
+        )
+    }
+
+    fn rule(&mut self, position: usize, key: String, rule: &Rule) -> String {
+        self.rule = position;
+        self.count = 0;
+        let location = format!("mergeable[{position}]");
+        let errors = self.compiled.rule(&location, rule);
+        // like its evaluation, a rule with a pattern which can't be compiled reports an error
+        let (filters, validators): (Vec<_>, Vec<_>) = if errors.is_empty() {
+            self.nodes(&location, rule)
+        } else {
+            let errors = errors
+                .iter()
+                .map(|err| {
+                    self.unconverted(
+                        err.location(),
+                        err.validator(),
+                        &format!("can't be compiled, {}", err.reason()),
+                    )
+                })
+                .collect();
+            (Vec::new(), errors)
+        };
+        let mut actions = serde_json::Map::new();
+        for status in [RuleStatus::Pass, RuleStatus::Fail, RuleStatus::Error] {
+            let configured = rule
+                .actions(status)
+                .into_iter()
+                .map(|action| serde_json::to_value(action).unwrap_or_default())
+                .collect();
+            actions.insert(status.as_str().to_owned(), JsonValue::Array(configured));
+        }
+        self.function(
+            "payload",
+            &format!(
+                "    return _rule({}, payload, [{}], [{}], {})\n",
+                string(&key),
+                filters.join(", "),
+                validators.join(", "),
+                literal(&JsonValue::Object(actions)),
+            ),
+        )
+    }
+
+    /// The functions of the filters and validators of the rule.
+    fn nodes(&mut self, location: &str, rule: &Rule) -> (Vec<String>, Vec<String>) {
+        let filters: Vec<_> = rule
+            .filter
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(position, filter)| {
+                self.filter(&format!("{location}.filter[{position}]"), filter)
+            })
+            .collect();
+        let validators: Vec<_> = rule
+            .validate
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(position, validator)| {
+                self.validator(&format!("{location}.validate[{position}]"), validator)
+            })
+            .collect();
+        (filters, validators)
+    }
+
+    fn filter(&mut self, location: &str, filter: &Filter) -> String {
+        match filter {
+            Filter::Author(author) if author.team.is_some() => {
+                self.unconverted(location, "author", "with a 'team' needs the team members")
+            }
+            Filter::Author(author) => self.author(location, &author.filter),
+            Filter::Repository(repository) if repository.topics.is_some() => self.unconverted(
+                location,
+                "repository",
+                "with 'topics' needs the repository topics",
+            ),
+            Filter::Repository(repository) => self.repository(location, repository),
+            Filter::Payload(payload) => {
+                let mut body =
+                    String::from("    vars = _vars(payload, \"payload\")\n    failures = []\n");
+                for (name, field) in [
+                    ("pull_request", &payload.pull_request),
+                    ("review", &payload.review),
+                    ("sender", &payload.sender),
+                ] {
+                    if let Some(field) = field {
+                        self.payload_field(
+                            &mut body,
+                            &format!("{location}.{name}"),
+                            &mut vec![name.to_owned()],
+                            field,
+                        );
+                    }
+                }
+                body.push_str(
+                    "    return _result(\"payload\", failures, \
+                     \"Payload satisfies the payload conditions\")\n",
+                );
+                self.function("payload", &body)
+            }
+            Filter::And(and) => {
+                let nested = self.filters(location, &and.0.filter);
+                self.composition("_all", "and", &nested)
+            }
+            Filter::Or(or) => {
+                let nested = self.filters(location, &or.0.filter);
+                self.composition("_any", "or", &nested)
+            }
+            Filter::Not(not) => {
+                let nested = self.filters(location, &not.0.filter);
+                self.composition("_none", "not", &nested)
+            }
+        }
+    }
+
+    fn filters(&mut self, location: &str, filters: &[Filter]) -> Vec<String> {
+        filters
+            .iter()
+            .enumerate()
+            .map(|(position, filter)| {
+                self.filter(&format!("{location}.filter[{position}]"), filter)
+            })
+            .collect()
+    }
+
+    fn validator(&mut self, location: &str, validator: &Validator) -> String {
+        match validator {
+            Validator::Assignee(assignee) => self.assignee(location, assignee),
+            Validator::Author(author) if author.team.is_some() => {
+                self.unconverted(location, "author", "with a 'team' needs the team members")
+            }
+            Validator::Author(author) => self.author(location, &author.author),
+            Validator::BaseRef(base_ref) => self.reference(
+                location,
+                "baseRef",
+                "base",
+                base_ref.include.as_ref(),
+                base_ref.exclude.as_ref(),
+            ),
+            Validator::Description(description) if description.jira.is_some() => {
+                self.unconverted(location, "description", "with 'jira' isn't supported")
+            }
+            Validator::Description(description) => self.text(
+                location,
+                "description",
+                "_item(payload).get(\"body\") or \"\"",
+                description.no_empty.as_ref(),
+                description.description.as_ref(),
+            ),
+            Validator::HeadRef(head_ref) if head_ref.jira.is_some() => {
+                self.unconverted(location, "headRef", "with 'jira' isn't supported")
+            }
+            Validator::HeadRef(head_ref) => self.reference(
+                location,
+                "headRef",
+                "head",
+                head_ref.include.as_ref(),
+                head_ref.exclude.as_ref(),
+            ),
+            Validator::Label(label) if label.jira.is_some() => {
+                self.unconverted(location, "label", "with 'jira' isn't supported")
+            }
+            Validator::Label(label) => self.labels(location, label),
+            Validator::Milestone(milestone) if milestone.jira.is_some() => {
+                self.unconverted(location, "milestone", "with 'jira' isn't supported")
+            }
+            Validator::Milestone(milestone) => self.milestone(location, milestone),
+            Validator::Title(title) if title.jira.is_some() => {
+                self.unconverted(location, "title", "with 'jira' isn't supported")
+            }
+            Validator::Title(title) => self.text(
+                location,
+                "title",
+                "_item(payload).get(\"title\") or \"\"",
+                title.no_empty.as_ref(),
+                Some(&title.title),
+            ),
+            Validator::And(and) => {
+                let nested = self.validators(location, &and.0.validate);
+                self.composition("_all", "and", &nested)
+            }
+            Validator::Or(or) => {
+                let nested = self.validators(location, &or.0.validate);
+                self.composition("_any", "or", &nested)
+            }
+            Validator::Not(not) => {
+                let nested = self.validators(location, &not.0.validate);
+                self.composition("_none", "not", &nested)
+            }
+            other => self.unconverted(location, other.name(), "needs more than the event payload"),
+        }
+    }
+
+    fn validators(&mut self, location: &str, validators: &[Validator]) -> Vec<String> {
+        validators
+            .iter()
+            .enumerate()
+            .map(|(position, validator)| {
+                self.validator(&format!("{location}.validate[{position}]"), validator)
+            })
+            .collect()
+    }
+
+    fn composition(&mut self, helper: &str, name: &str, nested: &[String]) -> String {
+        self.function(
+            "payload",
+            &format!(
+                "    return {helper}({}, [{}], payload)\n",
+                string(name),
+                nested.join(", ")
+            ),
+        )
+    }
+
+    fn assignee(&mut self, location: &str, assignee: &TheAssignee) -> String {
+        let mut body = String::from(
+            "    vars = _vars(payload, \"assignee\")\n    \
+             count = len(_item(payload).get(\"assignees\") or [])\n    failures = []\n",
+        );
+        self.count_clauses(
+            &mut body,
+            location,
+            "Assignee",
+            assignee.min.as_ref(),
+            assignee.max.as_ref(),
+        );
+        body.push_str(
+            "    return _result(\"assignee\", failures, \
+             \"%d assignees satisfy the assignee conditions\" % count)\n",
+        );
+        self.function("payload", &body)
+    }
+
+    fn author(&mut self, location: &str, clause: &ChainedAndOrIncludeExcludeClause) -> String {
+        let chain = self.chain(location, "author", clause);
+        self.function(
+            "payload",
+            &format!(
+                "    vars = _vars(payload, \"author\")\n    \
+                 author = (_item(payload).get(\"user\") or {{}}).get(\"login\") or \"\"\n    \
+                 return _result(\"author\", {chain}(vars, [author]), \
+                 \"%s satisfies the author conditions\" % author)\n"
+            ),
+        )
+    }
+
+    fn repository(&mut self, location: &str, repository: &TheRepository) -> String {
+        let mut body = String::from(
+            "    vars = _vars(payload, \"repository\")\n    \
+             repository = payload.get(\"repository\") or {}\n    failures = []\n",
+        );
+        if let Some(visibility) = repository.visibility {
+            let _ = write!(
+                body,
+                "    visibility = _visibility(repository)\n    \
+                 if visibility != {expected}:\n        \
+                 failures.append(\"Repository visibility is '%s' instead of '%s'\" % \
+                 (visibility, {expected}))\n",
+                expected = string(visibility.as_str()),
+            );
+        }
+        if let Some(name) = &repository.name {
+            let chain = self.chain(&format!("{location}.name"), "repository name", name);
+            let _ = writeln!(
+                body,
+                "    failures += {chain}(vars, [repository.get(\"name\") or \"\"])"
+            );
+        }
+        body.push_str(
+            "    return _result(\"repository\", failures, \
+             \"Repository satisfies the repository conditions\")\n",
+        );
+        self.function("payload", &body)
+    }
+
+    fn milestone(&mut self, location: &str, milestone: &TheMilestone) -> String {
+        let chain = self.chain(location, "milestone", &milestone.milestone);
+        let mut body = String::from(
+            "    vars = _vars(payload, \"milestone\")\n    \
+             title = (_item(payload).get(\"milestone\") or {}).get(\"title\")\n    \
+             if type(title) != \"string\":\n",
+        );
+        match &milestone.no_empty {
+            Some(no_empty) if no_empty.enabled => {
+                let message = self.no_empty(location, "The milestone can't be empty", no_empty);
+                let _ = writeln!(
+                    body,
+                    "        return _result(\"milestone\", [{message}], \"\")"
+                );
+            }
+            _ => body
+                .push_str("        return _result(\"milestone\", [], \"No milestone is set\")\n"),
+        }
+        let _ = writeln!(
+            body,
+            "    return _result(\"milestone\", {chain}(vars, [title]), \
+             \"The milestone '%s' satisfies the milestone conditions\" % title)"
+        );
+        self.function("payload", &body)
+    }
+
+    /// The title or description, as the Starlark expression `value`.
+    fn text<C: TextChain>(
+        &mut self,
+        location: &str,
+        name: &str,
+        value: &str,
+        no_empty: Option<&NoEmpty>,
+        chain: Option<&C>,
+    ) -> String {
+        let mut body = format!(
+            "    vars = _vars(payload, {name})\n    value = {value}\n",
+            name = string(name)
+        );
+        if let Some(no_empty) = no_empty.filter(|no_empty| no_empty.enabled) {
+            let message = self.no_empty(location, &format!("The {name} can't be empty"), no_empty);
+            let _ = writeln!(
+                body,
+                "    if not value.strip():\n        return _result({}, [{message}], \"\")",
+                string(name)
+            );
+        }
+        let failures = match chain {
+            Some(chain) => format!("{}(vars, [value])", self.chain(location, name, chain)),
+            None => "[]".to_owned(),
+        };
+        let _ = writeln!(
+            body,
+            "    return _result({name}, {failures}, \"The {subject} satisfies the {subject} \
+             conditions\")",
+            name = string(name),
+            subject = name,
+        );
+        self.function("payload", &body)
+    }
+
+    fn labels(&mut self, location: &str, label: &TheLabel) -> String {
+        let chain = self.chain(location, "label", &label.label);
+        let mut body = String::from(
+            "    vars = _vars(payload, \"label\")\n    \
+             labels = [label.get(\"name\") or \"\" for label in \
+             _item(payload).get(\"labels\") or []]\n",
+        );
+        if let Some(no_empty) = label.no_empty.as_ref().filter(|no_empty| no_empty.enabled) {
+            let message = self.no_empty(location, "The labels can't be empty", no_empty);
+            let _ = writeln!(
+                body,
+                "    if not labels:\n        return _result(\"label\", [{message}], \"\")"
+            );
+        }
+        let _ = writeln!(
+            body,
+            "    return _result(\"label\", {chain}(vars, labels), \
+             \"The labels satisfy the label conditions\")"
+        );
+        self.function("payload", &body)
+    }
+
+    /// The base or head ref of the pull request, or of the pull requests of a check suite.
+    fn reference(
+        &mut self,
+        location: &str,
+        name: &str,
+        side: &str,
+        include: Option<&MustInclude>,
+        exclude: Option<&MustExclude>,
+    ) -> String {
+        let mut body = format!(
+            "    vars = _vars(payload, {name})\n    values = _refs(payload, {side})\n    \
+             failures = []\n",
+            name = string(name),
+            side = string(side),
+        );
+        self.regex_clauses(
+            &mut body,
+            location,
+            &format!("{side} ref"),
+            include,
+            exclude,
+        );
+        let _ = writeln!(
+            body,
+            "    return _result({}, failures, \"The {side} ref '%s' satisfies the {name} \
+             conditions\" % \"', '\".join(values))",
+            string(name),
+        );
+        self.function("payload", &body)
+    }
+
+    /// A function of the vars and values, returning the failures of the clause.
+    fn chain<C: TextChain>(&mut self, location: &str, subject: &str, clause: &C) -> String {
+        let clauses = clause.clauses();
+        let mut body = String::from("    failures = []\n");
+        self.regex_clauses(
+            &mut body,
+            location,
+            subject,
+            clauses.include,
+            clauses.exclude,
+        );
+        self.match_clauses(
+            &mut body,
+            location,
+            subject,
+            clauses.begins_with,
+            clauses.ends_with,
+        );
+        let [and, or] = clause.operands().map(|(operand, nested)| {
+            nested
+                .unwrap_or_default()
+                .iter()
+                .enumerate()
+                .map(|(position, nested)| {
+                    self.chain(
+                        &format!("{location}.{operand}[{position}]"),
+                        subject,
+                        nested,
+                    )
+                })
+                .collect::<Vec<_>>()
+        });
+        nested_chains(&mut body, &and, clause.operands()[1].1.map(|_| or));
+        body.push_str("    return failures\n");
+        self.function("vars, values", &body)
+    }
+
+    fn regex_clauses(
+        &mut self,
+        body: &mut String,
+        location: &str,
+        subject: &str,
+        include: Option<&MustInclude>,
+        exclude: Option<&MustExclude>,
+    ) {
+        for (helper, name, clause) in [
+            (
+                "_include",
+                "must_include",
+                include.map(|include| &include.0),
+            ),
+            (
+                "_exclude",
+                "must_exclude",
+                exclude.map(|exclude| &exclude.0),
+            ),
+        ] {
+            let Some(clause) = clause else {
+                continue;
+            };
+            let message = self.message(&format!("{location}.{name}"), clause.message());
+            let _ = writeln!(
+                body,
+                "    failures += {helper}({}, {}, values, {message})",
+                string(subject),
+                regexes(&self.compiled, clause),
+            );
+        }
+    }
+
+    fn match_clauses(
+        &mut self,
+        body: &mut String,
+        location: &str,
+        subject: &str,
+        begins_with: Option<&BeginsWith>,
+        ends_with: Option<&EndsWith>,
+    ) {
+        for (helper, name, clause) in [
+            (
+                "_begins_with",
+                "begins_with",
+                begins_with.map(|begins_with| &begins_with.0),
+            ),
+            (
+                "_ends_with",
+                "ends_with",
+                ends_with.map(|ends_with| &ends_with.0),
+            ),
+        ] {
+            let Some(clause) = clause else {
+                continue;
+            };
+            let (patterns, clause_message) = match clause {
+                MatchClause::Long {
+                    match_clause,
+                    message,
+                } => (match_clause.as_slice(), message.as_ref()),
+                MatchClause::Short(pattern) => (std::slice::from_ref(pattern), None),
+            };
+            let patterns: Vec<_> = patterns.iter().map(|pattern| string(pattern)).collect();
+            let message = self.message(&format!("{location}.{name}"), clause_message);
+            let _ = writeln!(
+                body,
+                "    failures += {helper}({}, [{}], values, {message})",
+                string(subject),
+                patterns.join(", "),
+            );
+        }
+    }
+
+    fn count_clauses(
+        &mut self,
+        body: &mut String,
+        location: &str,
+        subject: &str,
+        min: Option<&Min>,
+        max: Option<&Max>,
+    ) {
+        for (helper, name, clause) in [
+            ("_min", "min", min.map(|min| &min.0)),
+            ("_max", "max", max.map(|max| &max.0)),
+        ] {
+            let Some(clause) = clause else {
+                continue;
+            };
+            let message = self.message(&format!("{location}.{name}"), clause.message.as_ref());
+            let _ = writeln!(
+                body,
+                "    failures += {helper}({}, count, {}, {message})",
+                string(subject),
+                clause.count,
+            );
+        }
+    }
+
+    fn payload_field(
+        &mut self,
+        body: &mut String,
+        location: &str,
+        path: &mut Vec<String>,
+        field: &PayloadField,
+    ) {
+        match field {
+            PayloadField::Nested(fields) => {
+                for (name, field) in fields {
+                    path.push(name.clone());
+                    self.payload_field(body, &format!("{location}.{name}"), path, field);
+                    path.pop();
+                }
+            }
+            PayloadField::Check(check) => self.payload_check(body, location, path, check),
+        }
+    }
+
+    fn payload_check(
+        &mut self,
+        body: &mut String,
+        location: &str,
+        path: &[String],
+        check: &PayloadCheck,
+    ) {
+        let subject = path.join(".");
+        let path: Vec<_> = path.iter().map(|name| string(name)).collect();
+        let _ = writeln!(body, "    value = _field(payload, [{}])", path.join(", "));
+        if let Some(boolean) = &check.boolean {
+            let expected = boolean.match_clause;
+            let failure = match &boolean.message {
+                Some(message) => self.message(&format!("{location}.boolean"), Some(message)),
+                None => string(&format!("{subject} is not {expected}")),
+            };
+            let _ = writeln!(
+                body,
+                "    if value != {}:\n        failures.append({failure})",
+                literal(&JsonValue::Bool(expected)),
+            );
+        }
+        for (helper, name, clause) in [
+            ("_include", "must_include", &check.include),
+            ("_exclude", "must_exclude", &check.exclude),
+        ] {
+            let Some(clause) = clause else {
+                continue;
+            };
+            let message = self.message(&format!("{location}.{name}"), clause.message.as_ref());
+            let _ = writeln!(
+                body,
+                "    failures += {helper}({}, {}, _values(value, {}), {message})",
+                string(&subject),
+                payload_regexes(&self.compiled, clause),
+                clause.key.as_deref().map_or("None".to_owned(), string),
+            );
+        }
+    }
+
+    /// The message of a `no_empty` clause, or the default one.
+    fn no_empty(&mut self, location: &str, default: &str, no_empty: &NoEmpty) -> String {
+        match &no_empty.message {
+            Some(message) => self.message(&format!("{location}.no_empty"), Some(message)),
+            None => string(default),
+        }
+    }
+
+    /// The message rendered with `vars`, `None` for the default message.
+    ///
+    /// Templates using more than the plain variables of the event are kept as written.
+    fn message(&mut self, location: &str, message: Option<&MessageClause>) -> String {
+        let Some(message) = message else {
+            return "None".to_owned();
+        };
+        let template = message.0.as_str();
+        render(template).unwrap_or_else(|| {
+            self.unconverted.push(Warning {
+                location: format!("{location}.message"),
+                message: format!(
+                    "the template '{template}' uses more than plain variables, it's kept as \
+                     written in Starlark"
+                ),
+            });
+            string(template)
+        })
+    }
+}
+
+/// The clauses of a [`TextChain`] itself, without its nested ones.
+#[derive(Default)]
+struct TextClauses<'a> {
+    include: Option<&'a MustInclude>,
+    exclude: Option<&'a MustExclude>,
+    begins_with: Option<&'a BeginsWith>,
+    ends_with: Option<&'a EndsWith>,
+}
+
+/// A condition on text with nested `and` and `or` conditions of the same shape.
+trait TextChain: Sized {
+    fn clauses(&self) -> TextClauses<'_>;
+
+    fn operands(&self) -> [(&'static str, Option<&[Self]>); 2];
+}
+
+/// Implements [`TextChain`] for a condition with `must_include`, `must_exclude`, `and` and `or`,
+/// and the listed match clauses.
+macro_rules! impl_text_chain {
+    ($($chain:ty $({ $($match:ident),+ })?),+ $(,)?) => {
+        $(
+            impl TextChain for $chain {
+                fn clauses(&self) -> TextClauses<'_> {
+                    #[allow(unused_mut)]
+                    let mut clauses = TextClauses {
+                        include: self.include.as_ref(),
+                        exclude: self.exclude.as_ref(),
+                        ..Default::default()
+                    };
+                    $($(clauses.$match = self.$match.as_ref();)+)?
+                    clauses
+                }
+
+                fn operands(&self) -> [(&'static str, Option<&[Self]>); 2] {
+                    [("and", self.and.as_deref()), ("or", self.or.as_deref())]
+                }
+            }
+        )+
+    };
+}
+
+impl_text_chain!(
+    ChainedAndOrIncludeExcludeClause,
+    ChainedAndOrIncludeExcludeClauseBeginsEnds {
+        begins_with,
+        ends_with
+    },
+    DescriptionChain {
+        begins_with,
+        ends_with
+    },
+    LabelChain {
+        begins_with,
+        ends_with
+    },
+    MilestoneChain {
+        begins_with,
+        ends_with
+    },
+);
+
+fn nested_chains(body: &mut String, and: &[String], or: Option<Vec<String>>) {
+    for nested in and {
+        let _ = writeln!(body, "    failures += {nested}(vars, values)");
+    }
+    if let Some(or) = or {
+        let clauses: Vec<_> = or
+            .iter()
+            .map(|nested| format!("{nested}(vars, values)"))
+            .collect();
+        let _ = writeln!(body, "    failures += _or([{}])", clauses.join(", "));
+    }
+}
+
+/// The variables of a message which the script knows, those of the event and the validator.
+const MESSAGE_VARS: [&str; 5] = ["author", "title", "number", "repository", "validator"];
+
+/// A Starlark expression rendering the template with `vars`, `None` if it uses more than the
+/// plain variables.
+fn render(template: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let (text, expression) = rest.split_at(start);
+        // an escaped expression is rendered as written
+        if text.ends_with('\\') {
+            return None;
+        }
+        let end = expression.find("}}")?;
+        let name = expression[2..end].trim();
+        if !MESSAGE_VARS.contains(&name) {
+            return None;
+        }
+        if !text.is_empty() {
+            parts.push(string(text));
+        }
+        parts.push(format!("vars[{}]", string(name)));
+        rest = &expression[end + 2..];
+    }
+    if !rest.is_empty() || parts.is_empty() {
+        parts.push(string(rest));
+    }
+    Some(parts.join(" + "))
+}
+
+fn regexes(compiled: &Compiled, clause: &RegexClause) -> String {
+    let (patterns, flags) = clause.patterns();
+    pairs(compiled, patterns, &flags)
+}
+
+fn payload_regexes(compiled: &Compiled, clause: &PayloadRegexClause) -> String {
+    let flags = clause.regex_flag.clone().unwrap_or_default();
+    pairs(compiled, clause.regex.as_slice(), &flags)
+}
+
+/// Pairs of the regex as configured, for messages, and as matched.
+fn pairs(compiled: &Compiled, patterns: &[Pattern], flags: &RegexFlags) -> String {
+    let regexes = compiled
+        .regexes(patterns, flags)
+        .expect("the rules with patterns which can't be compiled aren't converted");
+    let pairs: Vec<_> = patterns
+        .iter()
+        .zip(regexes.inline(flags))
+        .map(|(pattern, inline)| format!("({}, {})", string(pattern.as_str()), string(&inline)))
+        .collect();
+    format!("[{}]", pairs.join(", "))
+}
+
+fn string(value: &str) -> String {
+    literal(&JsonValue::from(value))
+}
+
+/// A Starlark literal of the value, which JSON matches but for `null`, `true` and `false`.
+fn literal(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => "None".to_owned(),
+        JsonValue::Bool(true) => "True".to_owned(),
+        JsonValue::Bool(false) => "False".to_owned(),
+        JsonValue::Number(_) | JsonValue::String(_) => value.to_string(),
+        JsonValue::Array(items) => {
+            let items: Vec<_> = items.iter().map(literal).collect();
+            format!("[{}]", items.join(", "))
+        }
+        JsonValue::Object(fields) => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", string(name), literal(value)))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value as JsonValue};
+
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn converted_rules_evaluate_like_the_configuration() {
+        let config = Configuration::from_yaml(indoc::indoc! {r#"
+            version: 2
+            mergeable:
+              - when: pull_request.*, issues.opened
+                name: ready
+                filter:
+                  - do: payload
+                    pull_request:
+                      draft:
+                        boolean:
+                          match: false
+                  - do: repository
+                    visibility: public
+                validate:
+                  - do: assignee
+                    min:
+                      count: 1
+                  - do: or
+                    validate:
+                      - do: author
+                        must_exclude:
+                          regex: '\[bot\]$'
+                      - do: milestone
+                        begins_with:
+                          match: v1.
+                  - do: title
+                    must_exclude:
+                      regex: ^wip\b
+                      message: '{{author}} has to finish #{{ number }} first'
+                  - do: label
+                    must_exclude:
+                      regex: (?<!not )do not merge
+                  - do: baseRef
+                    must_include:
+                      regex: ^main$
+                pass:
+                  - do: labels
+                    add: ready
+              - when: pull_request.opened
+                validate:
+                  - do: size
+                    lines:
+                      max:
+                        count: 500
+                  - do: description
+                    no_empty:
+                      enabled: true
+                      message: '{{#if title}}{{title}} needs a description{{/if}}'
+        "#})
+        .unwrap();
+
+        let conversion = config.to_starlark();
+        let unconverted: Vec<_> = conversion
+            .unconverted
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            unconverted,
+            [
+                "mergeable[1].validate[0]: 'size' needs more than the event payload, it reports an error in Starlark",
+                "mergeable[1].validate[1].no_empty.message: the template '{{#if title}}{{title}} needs a description{{/if}}' uses more than plain variables, it's kept as written in Starlark",
+            ]
+        );
+
+        let evaluate = |pull_request: JsonValue| {
+            let event = json!({
+                "name": "pull_request.opened",
+                "payload": {
+                    "pull_request": pull_request,
+                    "repository": {"name": "billing", "visibility": "public"},
+                },
+            });
+            event_processor::evaluate_event(&conversion.script, &event)
+                .unwrap()
+                .iter()
+                .map(|result| serde_json::from_str(result).unwrap())
+                .collect::<Vec<JsonValue>>()
+        };
+
+        let results = evaluate(json!({
+            "draft": false,
+            "number": 7,
+            "title": "fix: parser",
+            "assignees": [{"login": "octocat"}],
+            "user": {"login": "dependabot[bot]"},
+            "milestone": {"title": "v1.2"},
+            "labels": [{"name": "bug"}],
+            "base": {"ref": "main"},
+        }));
+        assert_eq!(results[0]["status"], "pass", "{}", results[0]);
+        assert_eq!(
+            results[0]["actions"],
+            json!([{"do": "labels", "add": "ready"}])
+        );
+        assert_eq!(results[1]["status"], "error");
+        assert_eq!(
+            results[1]["validators"][1]["description"],
+            "{{#if title}}{{title}} needs a description{{/if}}"
+        );
+
+        let results = evaluate(json!({
+            "draft": false,
+            "number": 7,
+            "title": "WIP parser",
+            "assignees": [{"login": "octocat"}],
+            "user": {"login": "octocat"},
+            "labels": [{"name": "Do not merge"}],
+            "base": {"ref": "release"},
+        }));
+        let descriptions: Vec<_> = results[0]["validators"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|validator| {
+                (
+                    validator["status"].as_str(),
+                    validator["description"].as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            descriptions[2..],
+            [
+                (Some("fail"), Some("octocat has to finish #7 first")),
+                (
+                    Some("fail"),
+                    Some("label must exclude '(?<!not )do not merge'")
+                ),
+                (Some("fail"), Some("base ref does not include '^main$'")),
+            ]
+        );
+
+        let results = evaluate(json!({"draft": true}));
+        assert_eq!(results[0]["status"], "skipped");
+    }
+}
@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+/// A handlebars template like `{{author}} has to add {{#each files}}{{this}} {{/each}}`.
+///
+/// Only the syntax is checked when the configuration is loaded, the referenced variables are
+/// resolved when the template is rendered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "String")]
+pub struct Template(String);
+
+impl Template {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for Template {
+    type Error = TemplateSyntaxError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        handlebars::Template::compile(&source).context(TemplateSyntaxSnafu)?;
+        Ok(Self(source))
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Invalid template: {source}"))]
+pub struct TemplateSyntaxError {
+    source: handlebars::TemplateError,
+}
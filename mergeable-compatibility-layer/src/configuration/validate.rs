@@ -2,15 +2,23 @@ use crate::configuration::basics::{
     ChainedAndOrIncludeExcludeClause, ChainedAndOrIncludeExcludeClauseBeginsEnds, CountClause,
     MessageClause, TimeClause,
 };
+use chrono::Weekday;
+use chrono_tz::Tz;
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use schemars::JsonSchema;
+use serde::de::value::Error as ValueError;
+use serde::de::IntoDeserializer;
 use serde::{Deserialize, Serialize};
+use snafu::{OptionExt, ResultExt, Snafu};
 
 use crate::configuration::options::{
     BeginsWith, EndsWith, Jira, Max, Min, MustExclude, MustInclude, NoEmpty, Required,
 };
+use crate::configuration::pattern::Pattern;
 
 use super::basics::OneOrMany;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "do")]
 #[serde(deny_unknown_fields)]
 pub enum Validator {
@@ -73,237 +81,327 @@ pub enum Validator {
     Not(ValidatorNot),
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
-pub struct ValidatorAnd(ValidatorStack);
-
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
-pub struct ValidatorOr(ValidatorStack);
-
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
-pub struct ValidatorNot(ValidatorStack);
-
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
-struct ValidatorStack {
+impl Validator {
+    /// The name of the validator as used by the `do` key of the configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Validator::Age(_) => "age",
+            Validator::Approvals(_) => "approvals",
+            Validator::Assignee(_) => "assignee",
+            Validator::Author(_) => "author",
+            Validator::BaseRef(_) => "baseRef",
+            Validator::ChangeSet(_) => "changeset",
+            Validator::Commit(_) => "commit",
+            Validator::Contents(_) => "contents",
+            Validator::Dependent(_) => "dependent",
+            Validator::Description(_) => "description",
+            Validator::HeadRef(_) => "headRef",
+            Validator::Label(_) => "label",
+            Validator::Milestone(_) => "milestone",
+            Validator::Project(_) => "project",
+            Validator::Size(_) => "size",
+            Validator::Stale(_) => "stale",
+            Validator::Title(_) => "title",
+            Validator::And(_) => "and",
+            Validator::Or(_) => "or",
+            Validator::Not(_) => "not",
+        }
+    }
+
+    /// The events the validator can be evaluated for, none for `and`, `or` and `not`, which
+    /// support what their validators support.
+    pub fn supported_events(&self) -> &'static [&'static str] {
+        const PULL_REQUEST: &[&str] = &["pull_request.*", "pull_request_review.*"];
+        const PULL_REQUEST_AND_ISSUES: &[&str] =
+            &["pull_request.*", "pull_request_review.*", "issues.*"];
+        match self {
+            Validator::Age(_)
+            | Validator::Approvals(_)
+            | Validator::Author(_)
+            | Validator::ChangeSet(_)
+            | Validator::Commit(_)
+            | Validator::Contents(_)
+            | Validator::Dependent(_)
+            | Validator::HeadRef(_)
+            | Validator::Size(_) => PULL_REQUEST,
+            Validator::Assignee(_)
+            | Validator::Description(_)
+            | Validator::Label(_)
+            | Validator::Milestone(_)
+            | Validator::Project(_)
+            | Validator::Title(_) => PULL_REQUEST_AND_ISSUES,
+            Validator::BaseRef(_) => &[
+                "pull_request.*",
+                "pull_request_review.*",
+                "check_suite.*",
+                "status.*",
+            ],
+            Validator::Stale(_) => &["schedule.repository"],
+            Validator::And(_) | Validator::Or(_) | Validator::Not(_) => &[],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorAnd(pub(crate) ValidatorStack);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorOr(pub(crate) ValidatorStack);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorNot(pub(crate) ValidatorStack);
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ValidatorStack {
     #[serde(rename = "validate")]
-    validate: Vec<Validator>,
+    pub(crate) validate: Vec<Validator>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheAge {
     #[serde(rename = "created_at")]
-    created_at: TimeClause,
+    pub(crate) created_at: TimeClause,
     #[serde(rename = "updated_at")]
-    updated_at: TimeClause,
+    pub(crate) updated_at: TimeClause,
+    /// Only count Monday to Friday towards the age.
+    #[serde(rename = "business_days")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) business_days: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheApprovals {
     #[serde(rename = "min")]
-    min: Min,
+    pub(crate) min: Min,
     #[serde(rename = "required")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    required: Option<Required>,
+    pub(crate) required: Option<Required>,
     #[serde(rename = "block")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    block: Option<ApprovalsBlock>,
+    pub(crate) block: Option<ApprovalsBlock>,
     #[serde(rename = "limit")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    limit: Option<ApprovalsLimit>,
+    pub(crate) limit: Option<ApprovalsLimit>,
     #[serde(rename = "exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<ApprovalsExclude>,
+    pub(crate) exclude: Option<ApprovalsExclude>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheAssignee {
     #[serde(rename = "min")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    min: Option<Min>,
+    pub(crate) min: Option<Min>,
     #[serde(rename = "max")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    max: Option<Max>,
+    pub(crate) max: Option<Max>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheAuthor {
     #[serde(flatten)]
-    author: ChainedAndOrIncludeExcludeClause,
+    pub(crate) author: ChainedAndOrIncludeExcludeClause,
     #[serde(rename = "team")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    team: Option<String>,
+    pub(crate) team: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheBaseRef {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "mediaType")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    media_type: Option<serde_yaml::Value>,
+    #[schemars(with = "Option<serde_json::Value>")]
+    pub(crate) media_type: Option<serde_yaml::Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheChangeset {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    changeset: ChangesetChain,
+    pub(crate) changeset: ChangesetChain,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheCommit {
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<Message>,
+    pub(crate) message: Option<Message>,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheContents {
     #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<Files>,
+    pub(crate) files: Option<Files>,
     #[serde(flatten)]
-    content: ContentsFilter,
+    pub(crate) content: ContentsFilter,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheDependent {
     #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<Vec<String>>,
+    pub(crate) files: Option<Vec<String>>,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
     #[serde(rename = "changed")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    changed: Option<ChangedFiles>,
+    pub(crate) changed: Option<ChangedFiles>,
+}
+
+pub(crate) fn glob(pattern: &str) -> Result<Glob, InvalidGlobError> {
+    // behave like minimatch, a single `*` doesn't cross directories
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .context(InvalidGlobSnafu { pattern })
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+pub(crate) fn glob_set(patterns: &[String]) -> Result<GlobSet, InvalidGlobError> {
+    let mut set = GlobSetBuilder::new();
+    for pattern in patterns {
+        set.add(glob(pattern)?);
+    }
+    set.build().context(InvalidGlobSnafu {
+        pattern: patterns.join(", "),
+    })
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Invalid glob pattern '{pattern}': {source}"))]
+pub struct InvalidGlobError {
+    pub(crate) pattern: String,
+    pub(crate) source: globset::Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheDescription {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    description: Option<DescriptionChain>,
+    pub(crate) description: Option<DescriptionChain>,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheHeadRef {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheLabel {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    label: LabelChain,
+    pub(crate) label: LabelChain,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheMilestone {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    milestone: MilestoneChain,
+    pub(crate) milestone: MilestoneChain,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheProject {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheSize {
     #[serde(rename = "match")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    r#match: Option<Vec<String>>,
+    pub(crate) r#match: Option<Vec<String>>,
     #[serde(rename = "ignore")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore: Option<Vec<String>>,
+    pub(crate) ignore: Option<Vec<String>>,
     #[serde(rename = "lines")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    lines: Option<LinesChain>,
+    pub(crate) lines: Option<LinesChain>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheStale {
     #[serde(rename = "days")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    days: Option<u32>,
+    pub(crate) days: Option<u32>,
     #[serde(rename = "type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    r#type: Option<OneOrMany<String> /*Vec<ResourceType>*/>,
+    pub(crate) r#type: Option<OneOrMany<ResourceTypes>>,
     #[serde(rename = "ignore_drafts")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_drafts: Option<bool>,
+    pub(crate) ignore_drafts: Option<bool>,
     #[serde(rename = "ignore_milestones")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_milestones: Option<bool>,
+    pub(crate) ignore_milestones: Option<bool>,
     #[serde(rename = "ignore_projects")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_projects: Option<bool>,
+    pub(crate) ignore_projects: Option<bool>,
     #[serde(rename = "label")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    label: Option<LabelMatch>,
+    pub(crate) label: Option<LabelMatch>,
     #[serde(rename = "time_constraint")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    time_constraint: Option<TimeConstraint>,
+    pub(crate) time_constraint: Option<TimeConstraint>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum ResourceType {
     #[serde(rename = "pull_request")]
     PullRequest,
@@ -311,292 +409,431 @@ pub enum ResourceType {
     Issues,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Resource types, which mergeable also accepts as a comma separated list like
+/// `pull_request, issues`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "String")]
+pub struct ResourceTypes(String);
+
+impl ResourceTypes {
+    pub fn types(&self) -> impl Iterator<Item = ResourceType> + '_ {
+        self.0
+            .split(',')
+            .filter_map(|item_type| ResourceType::parse(item_type).ok())
+    }
+}
+
+impl ResourceType {
+    fn parse(item_type: &str) -> Result<Self, ValueError> {
+        Self::deserialize(item_type.trim().into_deserializer())
+    }
+}
+
+impl TryFrom<String> for ResourceTypes {
+    type Error = ValueError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        for item_type in source.split(',') {
+            ResourceType::parse(item_type)?;
+        }
+        Ok(Self(source))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TheTitle {
     #[serde(rename = "no_empty")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    no_empty: Option<NoEmpty>,
+    pub(crate) no_empty: Option<NoEmpty>,
     #[serde(flatten)]
-    title: ChainedAndOrIncludeExcludeClauseBeginsEnds,
+    pub(crate) title: ChainedAndOrIncludeExcludeClauseBeginsEnds,
     #[serde(rename = "jira")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    jira: Option<Jira>,
+    pub(crate) jira: Option<Jira>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ApprovalsRequired {
     #[serde(rename = "reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    reviewers: Option<Vec<String>>,
+    pub(crate) reviewers: Option<Vec<String>>,
     #[serde(rename = "owners")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    owners: Option<bool>,
+    pub(crate) owners: Option<bool>,
     #[serde(rename = "assignees")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    assignees: Option<bool>,
+    pub(crate) assignees: Option<bool>,
     #[serde(rename = "requested_reviewers")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    requested_reviewers: Option<bool>,
+    pub(crate) requested_reviewers: Option<bool>,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ApprovalsBlock {
     #[serde(rename = "changes_requested")]
-    changes_requested: bool,
+    pub(crate) changes_requested: bool,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ApprovalsLimit {
     #[serde(rename = "teams")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    teams: Option<Vec<String>>,
+    pub(crate) teams: Option<Vec<String>>,
     #[serde(rename = "users")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    users: Option<Vec<String>>,
+    pub(crate) users: Option<Vec<String>>,
     #[serde(rename = "owners")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    owners: Option<bool>,
+    pub(crate) owners: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ApprovalsExclude {
     #[serde(rename = "users")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    users: Option<Vec<String>>,
+    pub(crate) users: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ChangesetChain {
     #[serde(rename = "and")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<ChangesetChain>>,
+    pub(crate) and: Option<Vec<ChangesetChain>>,
     #[serde(rename = "or")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<ChangesetChain>>,
+    pub(crate) or: Option<Vec<ChangesetChain>>,
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
+    pub(crate) begins_with: Option<BeginsWith>,
     #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) ends_with: Option<EndsWith>,
     #[serde(rename = "min")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    min: Option<Min>,
+    pub(crate) min: Option<Min>,
     #[serde(rename = "max")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    max: Option<Max>,
+    pub(crate) max: Option<Max>,
     #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<FilesContent>,
+    pub(crate) files: Option<FilesContent>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct FilesContent {
     #[serde(rename = "added")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    added: Option<bool>,
+    pub(crate) added: Option<bool>,
     #[serde(rename = "modified")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    modified: Option<bool>,
+    pub(crate) modified: Option<bool>,
     #[serde(rename = "removed")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    removed: Option<bool>,
+    pub(crate) removed: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Message {
     #[serde(rename = "regex")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    regex: Option<String>,
+    pub(crate) regex: Option<Pattern>,
     #[serde(rename = "message")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message: Option<MessageClause>,
+    pub(crate) message: Option<MessageClause>,
     #[serde(rename = "skip_merge")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    skip_merge: Option<bool>,
+    pub(crate) skip_merge: Option<bool>,
     #[serde(rename = "oldest_only")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    oldest_only: Option<bool>,
+    pub(crate) oldest_only: Option<bool>,
     #[serde(rename = "newest_only")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    newest_only: Option<bool>,
+    pub(crate) newest_only: Option<bool>,
     #[serde(rename = "single_commit_only")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    single_commit_only: Option<bool>,
+    pub(crate) single_commit_only: Option<bool>,
     #[serde(rename = "message_type")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    message_type: Option<String>,
+    pub(crate) message_type: Option<MessageType>,
+}
+
+/// The field of a commit the regex is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum MessageType {
+    #[serde(rename = "")]
+    Message,
+    #[serde(rename = "author_email")]
+    AuthorEmail,
+    #[serde(rename = "committer_email")]
+    CommitterEmail,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Files {
     #[serde(rename = "pr_diff")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pr_diff: Option<bool>,
+    pub(crate) pr_diff: Option<bool>,
     #[serde(rename = "ignore")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore: Option<Vec<String>>,
+    pub(crate) ignore: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ContentsFilter {
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
+    pub(crate) begins_with: Option<BeginsWith>,
     #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) ends_with: Option<EndsWith>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ChangedFiles {
     #[serde(rename = "file")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    file: Option<String>,
+    pub(crate) file: Option<String>,
     #[serde(rename = "files")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    files: Option<Vec<String>>,
+    pub(crate) files: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct DescriptionChain {
     #[serde(rename = "and")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<DescriptionChain>>,
+    pub(crate) and: Option<Vec<DescriptionChain>>,
     #[serde(rename = "or")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<DescriptionChain>>,
+    pub(crate) or: Option<Vec<DescriptionChain>>,
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
+    pub(crate) begins_with: Option<BeginsWith>,
     #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) ends_with: Option<EndsWith>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct LabelChain {
     #[serde(rename = "and")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<LabelChain>>,
+    pub(crate) and: Option<Vec<LabelChain>>,
     #[serde(rename = "or")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<LabelChain>>,
+    pub(crate) or: Option<Vec<LabelChain>>,
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
+    pub(crate) begins_with: Option<BeginsWith>,
     #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) ends_with: Option<EndsWith>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct MilestoneChain {
     #[serde(rename = "and")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    and: Option<Vec<MilestoneChain>>,
+    pub(crate) and: Option<Vec<MilestoneChain>>,
     #[serde(rename = "or")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    or: Option<Vec<MilestoneChain>>,
+    pub(crate) or: Option<Vec<MilestoneChain>>,
     #[serde(rename = "must_include")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    include: Option<MustInclude>,
+    pub(crate) include: Option<MustInclude>,
     #[serde(rename = "must_exclude")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    exclude: Option<MustExclude>,
+    pub(crate) exclude: Option<MustExclude>,
     #[serde(rename = "begins_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    begins_with: Option<BeginsWith>,
+    pub(crate) begins_with: Option<BeginsWith>,
     #[serde(rename = "ends_with")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ends_with: Option<EndsWith>,
+    pub(crate) ends_with: Option<EndsWith>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct LinesChain {
     #[serde(rename = "total")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    total: Option<CountClause>,
+    pub(crate) total: Option<CountClause>,
     #[serde(rename = "additions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    additions: Option<CountClause>,
+    pub(crate) additions: Option<CountClause>,
     #[serde(rename = "deletions")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    deletions: Option<CountClause>,
+    pub(crate) deletions: Option<CountClause>,
     #[serde(rename = "max")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    max: Option<Max>,
+    pub(crate) max: Option<Max>,
     #[serde(rename = "ignore_comments")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore_comments: Option<bool>,
+    pub(crate) ignore_comments: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct LabelMatch {
     #[serde(rename = "match")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    r#match: Option<Vec<String>>,
+    pub(crate) r#match: Option<Vec<String>>,
     #[serde(rename = "ignore")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    ignore: Option<Vec<String>>,
+    pub(crate) ignore: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TimeConstraint {
     #[serde(rename = "time_zone")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    time_zone: Option<String>,
+    pub(crate) time_zone: Option<TimeZone>,
     #[serde(rename = "hours_between")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    hours_between: Option<Vec<String>>,
+    #[schemars(with = "Option<Vec<String>>")]
+    pub(crate) hours_between: Option<HoursBetween>,
     #[serde(rename = "days_of_week")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    days_of_week: Option<Vec<String>>,
+    pub(crate) days_of_week: Option<Vec<DayOfWeek>>,
+}
+
+/// The first and the last hour of the day, both included, like `["9", "17"]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct HoursBetween {
+    pub(crate) from: u32,
+    pub(crate) to: u32,
+}
+
+impl TryFrom<Vec<String>> for HoursBetween {
+    type Error = InvalidHoursError;
+
+    fn try_from(hours: Vec<String>) -> Result<Self, Self::Error> {
+        let hour = |hour: &String| hour.trim().parse().ok().filter(|hour| *hour < 24);
+        match hours.as_slice() {
+            [from, to] => Option::zip(hour(from), hour(to)),
+            _ => None,
+        }
+        .map(|(from, to)| Self { from, to })
+        .context(InvalidHoursSnafu {
+            hours: hours.join(", "),
+        })
+    }
+}
+
+impl From<HoursBetween> for Vec<String> {
+    fn from(hours: HoursBetween) -> Self {
+        vec![hours.from.to_string(), hours.to.to_string()]
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Invalid hours '{hours}', expected the first and the last hour between 0 and 23"))]
+pub struct InvalidHoursError {
+    pub(crate) hours: String,
+}
+
+/// A time zone of the IANA database like `America/Los_Angeles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimeZone(#[schemars(with = "String")] pub(crate) Tz);
+
+impl TryFrom<String> for TimeZone {
+    type Error = UnknownTimeZoneError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        name.parse()
+            .ok()
+            .map(Self)
+            .context(UnknownTimeZoneSnafu { name })
+    }
+}
+
+impl From<TimeZone> for String {
+    fn from(time_zone: TimeZone) -> Self {
+        time_zone.0.name().to_owned()
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Unknown time zone '{name}'"))]
+pub struct UnknownTimeZoneError {
+    pub(crate) name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum DayOfWeek {
+    #[serde(rename = "Mon", alias = "Monday")]
+    Monday,
+    #[serde(rename = "Tue", alias = "Tuesday")]
+    Tuesday,
+    #[serde(rename = "Wed", alias = "Wednesday")]
+    Wednesday,
+    #[serde(rename = "Thu", alias = "Thursday")]
+    Thursday,
+    #[serde(rename = "Fri", alias = "Friday")]
+    Friday,
+    #[serde(rename = "Sat", alias = "Saturday")]
+    Saturday,
+    #[serde(rename = "Sun", alias = "Sunday")]
+    Sunday,
+}
+
+impl From<DayOfWeek> for Weekday {
+    fn from(day: DayOfWeek) -> Self {
+        match day {
+            DayOfWeek::Monday => Weekday::Mon,
+            DayOfWeek::Tuesday => Weekday::Tue,
+            DayOfWeek::Wednesday => Weekday::Wed,
+            DayOfWeek::Thursday => Weekday::Thu,
+            DayOfWeek::Friday => Weekday::Fri,
+            DayOfWeek::Saturday => Weekday::Sat,
+            DayOfWeek::Sunday => Weekday::Sun,
+        }
+    }
 }
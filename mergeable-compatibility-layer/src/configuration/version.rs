@@ -0,0 +1,308 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value as YamlValue};
+use snafu::{OptionExt, ResultExt, Snafu};
+
+use super::{Configuration, Rule};
+
+/// Mergeable's comment on stale items of version 1 configurations without a `message`.
+const DEFAULT_STALE_MESSAGE: &str = "There haven't been much activity here. This is stale. Is it \
+    still relevant? This is a friendly reminder to please resolve it. :-)";
+
+/// The `version` of a loaded configuration, version 1 is upgraded to version 2 on load.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub enum ConfigurationVersion {
+    #[default]
+    V2,
+}
+
+impl TryFrom<u32> for ConfigurationVersion {
+    type Error = UnsupportedVersionError;
+
+    fn try_from(version: u32) -> Result<Self, Self::Error> {
+        match version {
+            2 => Ok(ConfigurationVersion::V2),
+            version => UnsupportedVersionSnafu { version }.fail(),
+        }
+    }
+}
+
+impl From<ConfigurationVersion> for u32 {
+    fn from(version: ConfigurationVersion) -> Self {
+        match version {
+            ConfigurationVersion::V2 => 2,
+        }
+    }
+}
+
+impl JsonSchema for ConfigurationVersion {
+    fn schema_name() -> String {
+        "ConfigurationVersion".to_owned()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::Integer.into()),
+            enum_values: Some(vec![2.into()]),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Unsupported configuration version {version}, expected version 2"))]
+pub struct UnsupportedVersionError {
+    pub(crate) version: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Version {
+    version: Option<u32>,
+    mergeable: Option<YamlValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesV2 {
+    extends: Option<String>,
+    mergeable: Vec<Rule>,
+}
+
+/// `mergeable` of version 1, validators by name per kind of item, in the order of the file.
+///
+/// The oldest configurations list the validators of pull requests right below `mergeable`.
+#[derive(Debug, Deserialize)]
+struct MergeableV1 {
+    pull_requests: Option<Mapping>,
+    issues: Option<Mapping>,
+    #[serde(flatten)]
+    validators: Mapping,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigurationV1 {
+    mergeable: MergeableV1,
+}
+
+impl Configuration {
+    /// Parses `.github/mergeable.yml` of either version, upgrading version 1 to version 2.
+    ///
+    /// Without a `version` the shape of `mergeable` decides, a list of rules is version 2.
+    pub fn from_yaml(source: &str) -> Result<Self, ConfigurationError> {
+        let Version { version, mergeable } = serde_yaml::from_str(source).context(ParseSnafu)?;
+        match (version, mergeable) {
+            (Some(2), _) => serde_yaml::from_str(source).context(ParseSnafu),
+            (None, Some(YamlValue::Sequence(_))) => {
+                let RulesV2 { extends, mergeable } =
+                    serde_yaml::from_str(source).context(ParseSnafu)?;
+                Ok(Configuration {
+                    version: ConfigurationVersion::V2,
+                    extends,
+                    mergeable,
+                })
+            }
+            (Some(1) | None, _) => {
+                let v1: ConfigurationV1 = serde_yaml::from_str(source).context(ParseSnafu)?;
+                v1.mergeable.upgrade()
+            }
+            (Some(version), _) => UnknownVersionSnafu { version }.fail(),
+        }
+    }
+}
+
+impl MergeableV1 {
+    fn upgrade(self) -> Result<Configuration, ConfigurationError> {
+        let mut pull_requests = self.validators;
+        pull_requests.extend(self.pull_requests.unwrap_or_default());
+        let mut rules = Vec::new();
+        for (kind, when, item_type, validators) in [
+            (
+                "pull_requests",
+                "pull_request.*",
+                "pull_request",
+                pull_requests,
+            ),
+            (
+                "issues",
+                "issues.*",
+                "issues",
+                self.issues.unwrap_or_default(),
+            ),
+        ] {
+            let mut validate = Vec::new();
+            for (name, settings) in validators {
+                let name = name.as_str().context(InvalidSettingSnafu {
+                    path: format!("mergeable.{kind}"),
+                })?;
+                let path = format!("mergeable.{kind}.{name}");
+                if name == "stale" {
+                    rules.push(stale_rule(kind, item_type, settings, &path)?);
+                } else {
+                    validate.push(validator(name, settings, &path)?);
+                }
+            }
+            if validate.is_empty() {
+                continue;
+            }
+            let rule = yaml_map([
+                ("when", when.into()),
+                ("name", kind.replace('_', " ").into()),
+                ("validate", YamlValue::Sequence(validate)),
+            ]);
+            rules.push(rule_from(rule, &format!("mergeable.{kind}"))?);
+        }
+        Ok(Configuration {
+            version: ConfigurationVersion::V2,
+            extends: None,
+            mergeable: rules,
+        })
+    }
+}
+
+/// Translates the shorthands of version 1, a text is a regex the field must not match.
+fn validator(name: &str, settings: YamlValue, path: &str) -> Result<YamlValue, ConfigurationError> {
+    let mut validator = match (name, settings) {
+        ("title" | "label" | "description", YamlValue::String(regex)) => {
+            yaml_map([("must_exclude", yaml_map([("regex", regex.into())]))])
+        }
+        ("approvals", count @ YamlValue::Number(_)) => {
+            yaml_map([("min", yaml_map([("count", count)]))])
+        }
+        (_, settings @ YamlValue::Mapping(_)) => settings,
+        (_, YamlValue::Null) => YamlValue::Mapping(Mapping::new()),
+        _ => return InvalidSettingSnafu { path }.fail(),
+    };
+    if let YamlValue::Mapping(settings) = &mut validator {
+        settings.insert("do".into(), name.into());
+    }
+    Ok(validator)
+}
+
+/// Stale items are searched for periodically and receive a comment, the validator passes with
+/// the items it found.
+fn stale_rule(
+    kind: &str,
+    item_type: &str,
+    settings: YamlValue,
+    path: &str,
+) -> Result<Rule, ConfigurationError> {
+    let YamlValue::Mapping(mut settings) = settings else {
+        return InvalidSettingSnafu { path }.fail();
+    };
+    let message = settings
+        .remove("message")
+        .unwrap_or_else(|| DEFAULT_STALE_MESSAGE.into());
+    settings.insert("do".into(), "stale".into());
+    settings.insert("type".into(), item_type.into());
+    let comment = yaml_map([
+        ("do", "comment".into()),
+        ("payload", yaml_map([("body", message)])),
+    ]);
+    let rule = yaml_map([
+        ("when", "schedule.repository".into()),
+        ("name", format!("stale {}", kind.replace('_', " ")).into()),
+        ("validate", YamlValue::Sequence(vec![settings.into()])),
+        ("pass", YamlValue::Sequence(vec![comment])),
+    ]);
+    rule_from(rule, path)
+}
+
+fn rule_from(rule: YamlValue, path: &str) -> Result<Rule, ConfigurationError> {
+    serde_yaml::from_value(rule).context(InvalidV1Snafu { path })
+}
+
+fn yaml_map<const N: usize>(entries: [(&str, YamlValue); N]) -> YamlValue {
+    YamlValue::Mapping(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.into(), value))
+            .collect(),
+    )
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum ConfigurationError {
+    #[snafu(display("Invalid configuration: {source}"))]
+    Parse { source: serde_yaml::Error },
+    #[snafu(display("Unknown configuration version {version}, supported are 1 and 2"))]
+    UnknownVersion { version: u32 },
+    #[snafu(display("Unsupported setting '{path}' of the version 1 configuration"))]
+    InvalidSetting { path: String },
+    #[snafu(display("Invalid setting '{path}' of the version 1 configuration: {source}"))]
+    InvalidV1 {
+        path: String,
+        source: serde_yaml::Error,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::version::ConfigurationError;
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn version_1_is_upgraded_to_rules() {
+        let config = Configuration::from_yaml(indoc::indoc! {"
+            mergeable:
+              pull_requests:
+                title: 'wip|dnm'
+                approvals: 1
+                stale:
+                  days: 20
+              issues:
+                milestone:
+                  must_include:
+                    regex: 'v\\d+'
+        "})
+        .unwrap();
+
+        let upgraded = serde_yaml::to_value(&config).unwrap();
+        let expected: serde_yaml::Value = serde_yaml::from_str(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: schedule.repository
+                name: stale pull requests
+                validate:
+                  - do: stale
+                    days: 20
+                    type: pull_request
+                pass:
+                  - do: comment
+                    payload:
+                      body: There haven't been much activity here. This is stale. Is it still relevant? This is a friendly reminder to please resolve it. :-)
+              - when: pull_request.*
+                name: pull requests
+                validate:
+                  - do: title
+                    must_exclude:
+                      regex: 'wip|dnm'
+                  - do: approvals
+                    min:
+                      count: 1
+              - when: issues.*
+                name: issues
+                validate:
+                  - do: milestone
+                    must_include:
+                      regex: 'v\\d+'
+        "})
+        .unwrap();
+        assert_eq!(upgraded, expected);
+
+        assert!(Configuration::from_yaml("mergeable: [{when: issues.opened}]").is_ok());
+        assert!(matches!(
+            Configuration::from_yaml("version: 3\nmergeable: []"),
+            Err(ConfigurationError::UnknownVersion { version: 3 })
+        ));
+        assert!(
+            serde_yaml::from_str::<Configuration>("version: 3\nmergeable: []")
+                .unwrap_err()
+                .to_string()
+                .contains("Unsupported configuration version 3")
+        );
+    }
+}
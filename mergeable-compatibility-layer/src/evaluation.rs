@@ -0,0 +1,178 @@
+use snafu::{Backtrace, Snafu};
+
+use crate::configuration::compiled::Compiled;
+use crate::configuration::filter::Filter;
+use crate::configuration::validate::Validator;
+
+pub use context::{
+    ChangedFile, Clock, EvaluationContext, FileStatus, FixedClock, Item, SystemClock, Visibility,
+};
+pub use rule::{RuleResult, RuleStatus};
+
+pub mod age;
+pub mod assignee;
+pub mod author;
+mod clause;
+mod composition;
+pub mod context;
+pub mod dependent;
+pub mod milestone;
+pub mod payload;
+pub mod project;
+pub mod repository;
+pub mod rule;
+pub mod stale;
+pub mod template;
+
+/// Outcome of a single validator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Error,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Error => "error",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Status::Pass => "✔️",
+            Status::Fail => "❌",
+            Status::Error => "⚠️",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorResult {
+    pub name: &'static str,
+    pub status: Status,
+    pub description: String,
+    /// The results of the nested validators of `and`, `or` and `not`, as far as they were evaluated.
+    pub children: Vec<ValidatorResult>,
+    /// Numbers of the pull requests and issues a scheduled validator found, the actions of the
+    /// rule apply to each of them.
+    pub items: Vec<u64>,
+}
+
+impl ValidatorResult {
+    pub fn with_items(self, items: Vec<u64>) -> Self {
+        Self { items, ..self }
+    }
+
+    pub fn pass(name: &'static str, description: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Pass,
+            description: description.into(),
+            children: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+
+    pub fn fail(name: &'static str, description: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            description: description.into(),
+            children: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+
+    pub fn error(name: &'static str, description: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Error,
+            description: description.into(),
+            children: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+}
+
+/// A validator which can't be evaluated reports an error instead of aborting the whole rule.
+pub(crate) fn outcome(
+    name: &'static str,
+    result: Result<ValidatorResult, EvaluationError>,
+) -> ValidatorResult {
+    result.unwrap_or_else(|err| ValidatorResult::error(name, err.to_string()))
+}
+
+impl Validator {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        match self {
+            Validator::Age(age) => age.evaluate(context).await,
+            Validator::Assignee(assignee) => assignee.evaluate(context).await,
+            Validator::Author(author) => author.evaluate(context, compiled).await,
+            Validator::Dependent(dependent) => dependent.evaluate(context, compiled).await,
+            Validator::Milestone(milestone) => milestone.evaluate(context, compiled).await,
+            Validator::Project(project) => project.evaluate(context, compiled).await,
+            Validator::Stale(stale) => stale.evaluate(context).await,
+            Validator::And(and) => {
+                Ok(composition::all("and", &and.0.validate, context, compiled).await)
+            }
+            Validator::Or(or) => {
+                Ok(composition::any("or", &or.0.validate, context, compiled).await)
+            }
+            Validator::Not(not) => {
+                Ok(composition::none("not", &not.0.validate, context, compiled).await)
+            }
+            other => UnsupportedSnafu {
+                validator: other.name(),
+            }
+            .fail(),
+        }
+    }
+}
+
+impl Filter {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        match self {
+            Filter::Author(author) => author.evaluate(context, compiled).await,
+            Filter::Repository(repository) => repository.evaluate(context, compiled).await,
+            Filter::Payload(payload) => payload.evaluate(context, compiled).await,
+            Filter::And(and) => Ok(composition::all("and", &and.0.filter, context, compiled).await),
+            Filter::Or(or) => Ok(composition::any("or", &or.0.filter, context, compiled).await),
+            Filter::Not(not) => {
+                Ok(composition::none("not", &not.0.filter, context, compiled).await)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum EvaluationError {
+    #[snafu(display("Unable to fetch the {what} of the event"))]
+    Context {
+        what: &'static str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+        backtrace: Backtrace,
+    },
+    #[snafu(display("The event has no valid '{field}'"))]
+    MissingField { field: String },
+    #[snafu(display("Unable to render the template: {source}"))]
+    Render {
+        source: Box<handlebars::RenderError>,
+    },
+    #[snafu(display("The validator '{validator}' is not supported"))]
+    Unsupported { validator: &'static str },
+    #[snafu(display("The patterns '{patterns}' weren't compiled with the configuration"))]
+    NotCompiled { patterns: String },
+}
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Datelike, TimeDelta, Utc, Weekday};
+use serde_json::Value as JsonValue;
+use snafu::OptionExt;
+
+use crate::configuration::basics::TimeClause;
+use crate::configuration::validate::TheAge;
+use crate::evaluation::context::event_item;
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{EvaluationContext, EvaluationError, MissingFieldSnafu, ValidatorResult};
+
+const NAME: &str = "age";
+
+impl TheAge {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        let now = context.now();
+        let item = event_item(context.payload());
+        let vars = MessageVars::new(context, NAME);
+        let mut failures = Vec::new();
+        for (field, verb, clause) in [
+            ("created_at", "created", &self.created_at),
+            ("updated_at", "updated", &self.updated_at),
+        ] {
+            let timestamp = timestamp(item, field)?;
+            let age = self.age(timestamp, now);
+            if age < i64::from(clause.days) {
+                failures.push(self.failure(clause, verb, age, &vars)?);
+            }
+        }
+        if failures.is_empty() {
+            Ok(ValidatorResult::pass(NAME, "The age conditions are met"))
+        } else {
+            Ok(ValidatorResult::fail(NAME, failures.join("\n")))
+        }
+    }
+
+    /// The full days elapsed since the timestamp, skipping weekends if only business days count.
+    fn age(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+        if self.business_days != Some(true) {
+            return (now - since).num_days();
+        }
+        let mut day = since + TimeDelta::days(1);
+        let mut age = 0;
+        while day <= now {
+            if !matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                age += 1;
+            }
+            day += TimeDelta::days(1);
+        }
+        age
+    }
+
+    fn failure(
+        &self,
+        clause: &TimeClause,
+        verb: &str,
+        age: i64,
+        vars: &MessageVars<'_>,
+    ) -> Result<String, EvaluationError> {
+        if let Some(message) = &clause.message {
+            return message.render(vars);
+        }
+        let unit = match self.business_days {
+            Some(true) => "business days",
+            _ => "days",
+        };
+        Ok(format!(
+            "The item was {verb} {age} {unit} ago, but has to be at least {days} {unit} old",
+            days = clause.days,
+        ))
+    }
+}
+
+fn timestamp(item: Option<&JsonValue>, field: &str) -> Result<DateTime<Utc>, EvaluationError> {
+    item.and_then(|item| item.get(field))
+        .and_then(JsonValue::as_str)
+        .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .context(MissingFieldSnafu { field })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::validate::Validator;
+    use crate::evaluation::context::{FixedClock, TestContext};
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn age_is_measured_against_the_clock() {
+        let validator = |business_days: bool| -> Validator {
+            serde_yaml::from_str(&format!(
+                "
+                do: age
+                business_days: {business_days}
+                created_at:
+                  days: 3
+                updated_at:
+                  days: 1
+                  message: 'Let the pull request settle for a day'
+                "
+            ))
+            .unwrap()
+        };
+        // created on a Friday, evaluated on the following Tuesday
+        let context = TestContext {
+            clock: FixedClock("2024-03-26T12:00:00Z".parse().unwrap()),
+            payload: json!({
+                "pull_request": {
+                    "created_at": "2024-03-22T09:00:00Z",
+                    "updated_at": "2024-03-26T08:00:00Z"
+                }
+            }),
+            ..Default::default()
+        };
+
+        let result = validator(false)
+            .evaluate(&context, &Compiled::default())
+            .await
+            .unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(result.description, "Let the pull request settle for a day");
+
+        let result = validator(true)
+            .evaluate(&context, &Compiled::default())
+            .await
+            .unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(
+            result.description,
+            "The item was created 2 business days ago, but has to be at least 3 business days old\nLet the pull request settle for a day"
+        );
+    }
+}
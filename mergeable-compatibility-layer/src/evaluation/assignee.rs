@@ -0,0 +1,75 @@
+use serde_json::Value as JsonValue;
+
+use crate::configuration::validate::TheAssignee;
+use crate::evaluation::context::event_item;
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{EvaluationContext, EvaluationError, ValidatorResult};
+
+const NAME: &str = "assignee";
+
+impl TheAssignee {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        let count = event_item(context.payload())
+            .and_then(|item| item.get("assignees"))
+            .and_then(JsonValue::as_array)
+            .map_or(0, Vec::len);
+        let vars = MessageVars::new(context, NAME);
+        let mut failures = Vec::new();
+        if let Some(min) = &self.min {
+            failures.extend(min.failure("Assignee", count, &vars)?);
+        }
+        if let Some(max) = &self.max {
+            failures.extend(max.failure("Assignee", count, &vars)?);
+        }
+        if failures.is_empty() {
+            Ok(ValidatorResult::pass(
+                NAME,
+                format!("{count} assignees satisfy the assignee conditions"),
+            ))
+        } else {
+            Ok(ValidatorResult::fail(NAME, failures.join("\n")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::validate::Validator;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn assignees_are_counted_on_issues_and_pull_requests() {
+        let validator: Validator = serde_yaml::from_str(indoc::indoc! {"
+            do: assignee
+            min:
+              count: 1
+            max:
+              count: 2
+              message: 'Too many cooks'
+        "})
+        .unwrap();
+        let compiled = Compiled::of_validator(&validator);
+        let mut context = TestContext {
+            payload: json!({ "issue": { "assignees": [{ "login": "octocat" }] } }),
+            ..Default::default()
+        };
+
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Pass);
+
+        context.payload = json!({ "pull_request": { "assignees": [] } });
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(
+            result.description,
+            "Assignee count is 0, but at least 1 are required"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use snafu::ResultExt;
+
+use crate::configuration::basics::ChainedAndOrIncludeExcludeClause;
+use crate::configuration::compiled::Compiled;
+use crate::configuration::{filter, validate};
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{ContextSnafu, EvaluationContext, EvaluationError, ValidatorResult};
+
+const NAME: &str = "author";
+
+impl validate::TheAuthor {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        evaluate(&self.author, self.team.as_deref(), context, compiled).await
+    }
+}
+
+impl filter::TheAuthor {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        evaluate(&self.filter, self.team.as_deref(), context, compiled).await
+    }
+}
+
+async fn evaluate<C: EvaluationContext>(
+    clause: &ChainedAndOrIncludeExcludeClause,
+    team: Option<&str>,
+    context: &C,
+    compiled: &Compiled,
+) -> Result<ValidatorResult, EvaluationError> {
+    let author = context.author();
+    let vars = MessageVars::new(context, NAME);
+    let mut failures = clause.failures(compiled, NAME, &[author], &vars)?;
+    // the membership lookup costs an API call, which is pointless once the clauses failed
+    if let (true, Some(team)) = (failures.is_empty(), team) {
+        let members = context
+            .team_members(team)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ContextSnafu {
+                what: "team members",
+            })?;
+        if !members
+            .iter()
+            .any(|member| member.eq_ignore_ascii_case(author))
+        {
+            failures.push(format!("{author} is not a member of the team '{team}'"));
+        }
+    }
+    if failures.is_empty() {
+        Ok(ValidatorResult::pass(
+            NAME,
+            format!("{author} satisfies the author conditions"),
+        ))
+    } else {
+        Ok(ValidatorResult::fail(NAME, failures.join("\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::validate::Validator;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn author_has_to_be_a_team_member() {
+        let validator: Validator = serde_yaml::from_str(indoc::indoc! {"
+            do: author
+            must_exclude:
+              regex: '\\[bot\\]$'
+            team: 'acme/platform-team'
+        "})
+        .unwrap();
+        let compiled = Compiled::of_validator(&validator);
+        let mut context = TestContext {
+            author: "octocat".into(),
+            ..Default::default()
+        };
+        context
+            .teams
+            .insert("acme/platform-team".into(), vec!["OctoCat".into()]);
+
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Pass);
+
+        context.author = "hubot".into();
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(
+            result.description,
+            "hubot is not a member of the team 'acme/platform-team'"
+        );
+
+        context.author = "dependabot[bot]".into();
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(result.description, "author must exclude '\\[bot\\]$'");
+    }
+}
@@ -0,0 +1,267 @@
+use crate::configuration::basics::{
+    ChainedAndOrIncludeExcludeClause, MatchClause, MessageClause, RegexClause, RegexForm,
+};
+use crate::configuration::compiled::Compiled;
+use crate::configuration::options::{BeginsWith, EndsWith, Max, Min, MustExclude, MustInclude};
+use crate::configuration::pattern::{Pattern, RegexFlags, Regexes};
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::EvaluationError;
+
+impl RegexForm {
+    pub(crate) fn patterns(&self) -> (&[Pattern], RegexFlags) {
+        match self {
+            RegexForm::Long {
+                regex, regex_flag, ..
+            } => (regex.as_slice(), regex_flag.clone().unwrap_or_default()),
+            RegexForm::Short(regex) => (std::slice::from_ref(regex), RegexFlags::default()),
+        }
+    }
+}
+
+impl RegexClause {
+    pub(crate) fn patterns(&self) -> (&[Pattern], RegexFlags) {
+        self.form.patterns()
+    }
+
+    /// The regexes compiled for the clause with its flags.
+    pub(crate) fn regexes<'a>(
+        &self,
+        compiled: &'a Compiled,
+    ) -> Result<&'a Regexes, EvaluationError> {
+        let (patterns, flags) = self.patterns();
+        compiled.regexes(patterns, &flags)
+    }
+
+    pub(crate) fn message(&self) -> Option<&MessageClause> {
+        match &self.form {
+            RegexForm::Long { message, .. } => message.as_ref(),
+            RegexForm::Short(_) => None,
+        }
+    }
+}
+
+impl MustInclude {
+    /// Every regex has to match at least one of the values.
+    pub(crate) fn failures(
+        &self,
+        compiled: &Compiled,
+        subject: &str,
+        values: &[&str],
+        vars: &MessageVars<'_>,
+    ) -> Result<Vec<String>, EvaluationError> {
+        self.0
+            .regexes(compiled)?
+            .iter()
+            .filter(|regex| !values.iter().any(|value| regex.is_match(value)))
+            .map(|regex| match self.0.message() {
+                Some(message) => message.render(vars),
+                None => Ok(format!("{subject} does not include '{regex}'")),
+            })
+            .collect()
+    }
+}
+
+impl MustExclude {
+    /// None of the regexes may match any of the values.
+    pub(crate) fn failures(
+        &self,
+        compiled: &Compiled,
+        subject: &str,
+        values: &[&str],
+        vars: &MessageVars<'_>,
+    ) -> Result<Vec<String>, EvaluationError> {
+        self.0
+            .regexes(compiled)?
+            .iter()
+            .filter(|regex| values.iter().any(|value| regex.is_match(value)))
+            .map(|regex| match self.0.message() {
+                Some(message) => message.render(vars),
+                None => Ok(format!("{subject} must exclude '{regex}'")),
+            })
+            .collect()
+    }
+}
+
+impl MatchClause {
+    fn patterns(&self) -> &[String] {
+        match self {
+            MatchClause::Long { match_clause, .. } => match_clause.as_slice(),
+            MatchClause::Short(pattern) => std::slice::from_ref(pattern),
+        }
+    }
+
+    fn failure(
+        &self,
+        vars: &MessageVars<'_>,
+        description: impl FnOnce(String) -> String,
+    ) -> Result<String, EvaluationError> {
+        match self {
+            MatchClause::Long {
+                message: Some(message),
+                ..
+            } => message.render(vars),
+            _ => Ok(description(self.patterns().join("' or '"))),
+        }
+    }
+}
+
+impl BeginsWith {
+    /// Every value has to begin with one of the patterns.
+    pub(crate) fn failures(
+        &self,
+        subject: &str,
+        values: &[&str],
+        vars: &MessageVars<'_>,
+    ) -> Result<Vec<String>, EvaluationError> {
+        let patterns = self.0.patterns();
+        values
+            .iter()
+            .filter(|value| !patterns.iter().any(|pattern| value.starts_with(pattern)))
+            .map(|_| {
+                self.0.failure(vars, |patterns| {
+                    format!("{subject} does not begin with '{patterns}'")
+                })
+            })
+            .collect()
+    }
+}
+
+impl EndsWith {
+    /// Every value has to end with one of the patterns.
+    pub(crate) fn failures(
+        &self,
+        subject: &str,
+        values: &[&str],
+        vars: &MessageVars<'_>,
+    ) -> Result<Vec<String>, EvaluationError> {
+        let patterns = self.0.patterns();
+        values
+            .iter()
+            .filter(|value| !patterns.iter().any(|pattern| value.ends_with(pattern)))
+            .map(|_| {
+                self.0.failure(vars, |patterns| {
+                    format!("{subject} does not end with '{patterns}'")
+                })
+            })
+            .collect()
+    }
+}
+
+impl Min {
+    pub(crate) fn failure(
+        &self,
+        subject: &str,
+        count: usize,
+        vars: &MessageVars<'_>,
+    ) -> Result<Option<String>, EvaluationError> {
+        if count >= self.0.count as usize {
+            return Ok(None);
+        }
+        match &self.0.message {
+            Some(message) => message.render(vars).map(Some),
+            None => Ok(Some(format!(
+                "{subject} count is {count}, but at least {min} are required",
+                min = self.0.count
+            ))),
+        }
+    }
+}
+
+impl Max {
+    pub(crate) fn failure(
+        &self,
+        subject: &str,
+        count: usize,
+        vars: &MessageVars<'_>,
+    ) -> Result<Option<String>, EvaluationError> {
+        if count <= self.0.count as usize {
+            return Ok(None);
+        }
+        match &self.0.message {
+            Some(message) => message.render(vars).map(Some),
+            None => Ok(Some(format!(
+                "{subject} count is {count}, but at most {max} are allowed",
+                max = self.0.count
+            ))),
+        }
+    }
+}
+
+impl ChainedAndOrIncludeExcludeClause {
+    /// Descriptions of every part of the clause the values don't satisfy, empty if all pass.
+    pub(crate) fn failures(
+        &self,
+        compiled: &Compiled,
+        subject: &str,
+        values: &[&str],
+        vars: &MessageVars<'_>,
+    ) -> Result<Vec<String>, EvaluationError> {
+        let mut failures = Vec::new();
+        if let Some(include) = &self.include {
+            failures.extend(include.failures(compiled, subject, values, vars)?);
+        }
+        if let Some(exclude) = &self.exclude {
+            failures.extend(exclude.failures(compiled, subject, values, vars)?);
+        }
+        for clause in self.and.iter().flatten() {
+            failures.extend(clause.failures(compiled, subject, values, vars)?);
+        }
+        if let Some(clauses) = &self.or {
+            let mut or_failures = Vec::new();
+            for clause in clauses {
+                let clause_failures = clause.failures(compiled, subject, values, vars)?;
+                if clause_failures.is_empty() {
+                    or_failures.clear();
+                    break;
+                }
+                or_failures.extend(clause_failures);
+            }
+            failures.extend(or_failures);
+        }
+        Ok(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::filter::Filter;
+    use crate::evaluation::template::MessageVars;
+
+    #[test]
+    fn chained_clauses_match_case_insensitive_by_default() {
+        let filter: Filter = serde_yaml::from_str(indoc::indoc! {"
+            do: author
+            must_exclude:
+              regex: 'bot$'
+            or:
+              - must_include:
+                  regex: '^alice$'
+              - must_include:
+                  regex: '^bob$'
+                  regex_flag: none
+        "})
+        .unwrap();
+        let compiled = Compiled::of_filter(&filter);
+        let Filter::Author(author) = &filter else {
+            unreachable!("deserialized as the author filter");
+        };
+        let failures = |name| {
+            author
+                .filter
+                .failures(&compiled, "author", &[name], &MessageVars::default())
+        };
+
+        assert!(failures("Alice").unwrap().is_empty());
+        assert!(failures("bob").unwrap().is_empty());
+        assert_eq!(failures("Bob").unwrap().len(), 2);
+        assert_eq!(
+            failures("alice-bot").unwrap(),
+            vec![
+                "author must exclude 'bot$'",
+                "author does not include '^alice$'",
+                "author does not include '^bob$'"
+            ]
+        );
+    }
+}
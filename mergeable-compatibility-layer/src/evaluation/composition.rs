@@ -0,0 +1,208 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::configuration::compiled::Compiled;
+use crate::configuration::filter::Filter;
+use crate::configuration::validate::Validator;
+use crate::evaluation::{outcome, EvaluationContext, EvaluationError, Status, ValidatorResult};
+
+type Evaluation<'a> =
+    Pin<Box<dyn Future<Output = Result<ValidatorResult, EvaluationError>> + Send + 'a>>;
+
+/// Filters and validators which can be nested in `and`, `or` and `not`.
+pub(crate) trait Composable: Sync {
+    fn name(&self) -> &'static str;
+
+    /// Boxed, as the nested validators may be compositions themselves.
+    fn evaluate_nested<'a, C: EvaluationContext>(
+        &'a self,
+        context: &'a C,
+        compiled: &'a Compiled,
+    ) -> Evaluation<'a>;
+}
+
+impl Composable for Validator {
+    fn name(&self) -> &'static str {
+        Validator::name(self)
+    }
+
+    fn evaluate_nested<'a, C: EvaluationContext>(
+        &'a self,
+        context: &'a C,
+        compiled: &'a Compiled,
+    ) -> Evaluation<'a> {
+        Box::pin(self.evaluate(context, compiled))
+    }
+}
+
+impl Composable for Filter {
+    fn name(&self) -> &'static str {
+        Filter::name(self)
+    }
+
+    fn evaluate_nested<'a, C: EvaluationContext>(
+        &'a self,
+        context: &'a C,
+        compiled: &'a Compiled,
+    ) -> Evaluation<'a> {
+        Box::pin(self.evaluate(context, compiled))
+    }
+}
+
+/// Passes if every nested validator passes, stopping at the first one which doesn't.
+pub(crate) async fn all<C: EvaluationContext, N: Composable>(
+    name: &'static str,
+    nested: &[N],
+    context: &C,
+    compiled: &Compiled,
+) -> ValidatorResult {
+    let mut children = Vec::new();
+    for node in nested {
+        let child = outcome(node.name(), node.evaluate_nested(context, compiled).await);
+        let status = child.status;
+        children.push(child);
+        if status != Status::Pass {
+            return composite(name, status, children);
+        }
+    }
+    composite(name, Status::Pass, children)
+}
+
+/// Passes if any nested validator passes, stopping at the first one which does.
+pub(crate) async fn any<C: EvaluationContext, N: Composable>(
+    name: &'static str,
+    nested: &[N],
+    context: &C,
+    compiled: &Compiled,
+) -> ValidatorResult {
+    let mut children = Vec::new();
+    for node in nested {
+        let child = outcome(node.name(), node.evaluate_nested(context, compiled).await);
+        let status = child.status;
+        children.push(child);
+        if status == Status::Pass {
+            return composite(name, Status::Pass, children);
+        }
+    }
+    // an error might have hidden a pass, so it can't be reported as a plain failure
+    let status = match children.iter().any(|child| child.status == Status::Error) {
+        true => Status::Error,
+        false => Status::Fail,
+    };
+    composite(name, status, children)
+}
+
+/// The negation of [`all`]: passes if at least one nested validator fails.
+pub(crate) async fn none<C: EvaluationContext, N: Composable>(
+    name: &'static str,
+    nested: &[N],
+    context: &C,
+    compiled: &Compiled,
+) -> ValidatorResult {
+    let inner = all(name, nested, context, compiled).await;
+    let status = match inner.status {
+        Status::Pass => Status::Fail,
+        Status::Fail => Status::Pass,
+        Status::Error => Status::Error,
+    };
+    composite(name, status, inner.children)
+}
+
+/// Explains the result as a tree of the nested results, e.g.
+///
+/// ```text
+/// ❌ author: octocat must exclude 'cat$'
+/// ❌ and
+///   ✔️ milestone: The milestone 'v1.2' satisfies the milestone conditions
+///   ❌ project: project does not include '^roadmap'
+/// ```
+fn composite(
+    name: &'static str,
+    status: Status,
+    children: Vec<ValidatorResult>,
+) -> ValidatorResult {
+    let description = children
+        .iter()
+        .map(|child| {
+            let icon = child.status.icon();
+            if child.children.is_empty() {
+                indent(&format!("{icon} {}: {}", child.name, child.description))
+            } else {
+                format!("{icon} {}\n{}", child.name, indent_all(&child.description))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut items: Vec<u64> = children
+        .iter()
+        .flat_map(|child| child.items.iter().copied())
+        .collect();
+    items.sort_unstable();
+    items.dedup();
+    ValidatorResult {
+        name,
+        status,
+        description,
+        children,
+        items,
+    }
+}
+
+/// Aligns the continuation lines of a multi-line description with its first line.
+fn indent(text: &str) -> String {
+    text.replace('\n', "\n  ")
+}
+
+fn indent_all(text: &str) -> String {
+    format!("  {}", indent(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::validate::Validator;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn compositions_short_circuit_and_explain_the_failure() {
+        let validator: Validator = serde_yaml::from_str(indoc::indoc! {"
+            do: and
+            validate:
+              - do: or
+                validate:
+                  - do: author
+                    must_include:
+                      regex: '^octocat$'
+                  - do: stale
+              - do: not
+                validate:
+                  - do: author
+                    must_exclude:
+                      regex: 'bot'
+                  - do: assignee
+                    max:
+                      count: 1
+        "})
+        .unwrap();
+        let compiled = Compiled::of_validator(&validator);
+        let context = TestContext {
+            author: "octocat".into(),
+            ..Default::default()
+        };
+
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        // the stale validator would search the repository, but the author already satisfied `or`
+        assert!(context.searches.lock().unwrap().is_empty());
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(
+            result.description,
+            indoc::indoc! {"
+                ✔️ or
+                  ✔️ author: octocat satisfies the author conditions
+                ❌ not
+                  ✔️ author: octocat satisfies the author conditions
+                  ✔️ assignee: 0 assignees satisfy the assignee conditions"}
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// Access to the data of the event a rule is evaluated against.
+///
+/// Everything is fetched lazily, so validators that are never reached don't cost an API call.
+pub trait EvaluationContext: Sync {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// The point in time the event is evaluated at.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Login of the user who opened the pull request or issue.
+    fn author(&self) -> &str;
+
+    /// The webhook payload as received from GitHub.
+    fn payload(&self) -> &serde_json::Value;
+
+    /// Name of the repository, without its owner.
+    fn repository_name(&self) -> &str;
+
+    fn repository_visibility(&self) -> Visibility;
+
+    /// Topics of the repository, which aren't part of every event payload.
+    fn repository_topics(&self) -> impl Future<Output = Result<Vec<String>, Self::Error>> + Send;
+
+    /// All files touched by the pull request.
+    fn changed_files(&self) -> impl Future<Output = Result<Vec<ChangedFile>, Self::Error>> + Send;
+
+    /// Names of the project boards, classic and Projects v2, the pull request or issue belongs to.
+    fn projects(&self) -> impl Future<Output = Result<Vec<String>, Self::Error>> + Send;
+
+    /// Issues and pull requests of the repository matching the given search qualifiers.
+    fn search_items(
+        &self,
+        qualifiers: &str,
+    ) -> impl Future<Output = Result<Vec<Item>, Self::Error>> + Send;
+
+    /// Logins of all members of a team, given as `org/team-slug`.
+    fn team_members(
+        &self,
+        team: &str,
+    ) -> impl Future<Output = Result<Vec<String>, Self::Error>> + Send;
+}
+
+/// The pull request or issue an event is about.
+pub(crate) fn event_item(payload: &serde_json::Value) -> Option<&serde_json::Value> {
+    payload.get("pull_request").or_else(|| payload.get("issue"))
+}
+
+/// Source of the current time, replaceable to keep evaluations deterministic.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock which is stopped at the given point in time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub filename: String,
+    pub status: FileStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Added,
+    Removed,
+    Modified,
+    Renamed,
+    Copied,
+    Changed,
+    Unchanged,
+}
+
+/// An issue or pull request found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    pub number: u64,
+    pub title: String,
+    pub pull_request: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    Public,
+    Private,
+    Internal,
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Private => "private",
+            Visibility::Internal => "internal",
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct TestContext {
+    pub(crate) clock: FixedClock,
+    pub(crate) author: String,
+    pub(crate) payload: serde_json::Value,
+    pub(crate) repository_name: String,
+    pub(crate) repository_private: bool,
+    pub(crate) repository_topics: Vec<String>,
+    pub(crate) changed_files: Vec<ChangedFile>,
+    pub(crate) items: Vec<Item>,
+    pub(crate) projects: Vec<String>,
+    pub(crate) searches: std::sync::Mutex<Vec<String>>,
+    pub(crate) teams: std::collections::HashMap<String, Vec<String>>,
+}
+
+#[cfg(test)]
+impl EvaluationContext for TestContext {
+    type Error = std::convert::Infallible;
+
+    fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    fn author(&self) -> &str {
+        &self.author
+    }
+
+    fn payload(&self) -> &serde_json::Value {
+        &self.payload
+    }
+
+    fn repository_name(&self) -> &str {
+        &self.repository_name
+    }
+
+    fn repository_visibility(&self) -> Visibility {
+        match self.repository_private {
+            true => Visibility::Private,
+            false => Visibility::Public,
+        }
+    }
+
+    async fn repository_topics(&self) -> Result<Vec<String>, Self::Error> {
+        Ok(self.repository_topics.clone())
+    }
+
+    async fn changed_files(&self) -> Result<Vec<ChangedFile>, Self::Error> {
+        Ok(self.changed_files.clone())
+    }
+
+    async fn projects(&self) -> Result<Vec<String>, Self::Error> {
+        Ok(self.projects.clone())
+    }
+
+    async fn search_items(&self, qualifiers: &str) -> Result<Vec<Item>, Self::Error> {
+        self.searches.lock().unwrap().push(qualifiers.to_owned());
+        Ok(self.items.clone())
+    }
+
+    async fn team_members(&self, team: &str) -> Result<Vec<String>, Self::Error> {
+        Ok(self.teams.get(team).cloned().unwrap_or_default())
+    }
+}
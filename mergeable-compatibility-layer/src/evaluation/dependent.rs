@@ -0,0 +1,181 @@
+use std::collections::BTreeSet;
+
+use globset::GlobSet;
+use snafu::ResultExt;
+
+use crate::configuration::compiled::Compiled;
+use crate::configuration::validate::TheDependent;
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{
+    ChangedFile, ContextSnafu, EvaluationContext, EvaluationError, ValidatorResult,
+};
+
+const NAME: &str = "dependent";
+
+impl TheDependent {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        let changed = context
+            .changed_files()
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ContextSnafu {
+                what: "changed files",
+            })?;
+        let violations = self.violations(compiled, &changed)?;
+        if violations.is_empty() {
+            return Ok(ValidatorResult::pass(
+                NAME,
+                "All dependent files are present",
+            ));
+        }
+        let description = match &self.message {
+            Some(message) => message.render(&MessageVars {
+                files: violations
+                    .iter()
+                    .flat_map(|violation| &violation.missing)
+                    .map(String::as_str)
+                    .collect(),
+                ..MessageVars::new(context, NAME)
+            })?,
+            None => violations
+                .iter()
+                .map(|violation| {
+                    format!(
+                        "One or more files ({missing}) are missing from your pull request because they are dependent on the following: {trigger}",
+                        missing = violation.missing.join(", "),
+                        trigger = violation.trigger.join(", "),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        Ok(ValidatorResult::fail(NAME, description))
+    }
+
+    /// Every group of dependent files which was triggered by the change but isn't complete.
+    fn violations(
+        &self,
+        compiled: &Compiled,
+        changed: &[ChangedFile],
+    ) -> Result<Vec<Violation>, EvaluationError> {
+        let mut violations = Vec::new();
+        // any of `files` changed means all of `files` have to be changed
+        if let Some(files) = &self.files {
+            let present = matched(compiled.globs(files)?, changed);
+            let (present, missing): (Vec<_>, Vec<_>) = files
+                .iter()
+                .enumerate()
+                .partition(|(index, _)| present.contains(index));
+            if !present.is_empty() && !missing.is_empty() {
+                violations.push(Violation {
+                    trigger: present.into_iter().map(|(_, file)| file.clone()).collect(),
+                    missing: missing.into_iter().map(|(_, file)| file.clone()).collect(),
+                });
+            }
+        }
+        // `changed.file` changed means all of `changed.files` have to be changed
+        if let Some(dependency) = &self.changed {
+            let (Some(file), Some(files)) = (&dependency.file, &dependency.files) else {
+                return Ok(violations);
+            };
+            let trigger = compiled.globs(std::slice::from_ref(file))?;
+            if changed
+                .iter()
+                .any(|changed| trigger.is_match(&changed.filename))
+            {
+                let present = matched(compiled.globs(files)?, changed);
+                let missing: Vec<_> = files
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !present.contains(index))
+                    .map(|(_, file)| file.clone())
+                    .collect();
+                if !missing.is_empty() {
+                    violations.push(Violation {
+                        trigger: vec![file.clone()],
+                        missing,
+                    });
+                }
+            }
+        }
+        Ok(violations)
+    }
+}
+
+struct Violation {
+    trigger: Vec<String>,
+    missing: Vec<String>,
+}
+
+/// The indices of the patterns of the set matching any of the changed files.
+fn matched(set: &GlobSet, changed: &[ChangedFile]) -> BTreeSet<usize> {
+    changed
+        .iter()
+        .flat_map(|file| set.matches(&file.filename))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::validate::Validator;
+    use crate::evaluation::{ChangedFile, FileStatus};
+
+    fn changed(files: &[&str]) -> Vec<ChangedFile> {
+        files
+            .iter()
+            .map(|filename| ChangedFile {
+                filename: filename.to_string(),
+                status: FileStatus::Modified,
+            })
+            .collect()
+    }
+
+    fn dependent(yaml: &str) -> (Validator, Compiled) {
+        let validator: Validator = serde_yaml::from_str(yaml).unwrap();
+        let compiled = Compiled::of_validator(&validator);
+        (validator, compiled)
+    }
+
+    #[test]
+    fn files_must_change_together() {
+        let (validator, compiled) =
+            dependent("{do: dependent, files: ['package.json', 'package-lock.json']}");
+        let Validator::Dependent(dependent) = &validator else {
+            unreachable!("deserialized as the dependent validator");
+        };
+        let violations = |files: &[&str]| dependent.violations(&compiled, &changed(files)).unwrap();
+
+        let violated = violations(&["package.json"]);
+        assert_eq!(violated.len(), 1);
+        assert_eq!(violated[0].missing, vec!["package-lock.json"]);
+
+        assert!(violations(&["package.json", "package-lock.json"]).is_empty());
+        assert!(violations(&["README.md"]).is_empty());
+    }
+
+    #[test]
+    fn changed_file_requires_globbed_dependents() {
+        let (validator, compiled) = dependent(indoc::indoc! {"
+            do: dependent
+            changed:
+              file: 'src/**/*.rs'
+              files: ['Cargo.lock', 'docs/*.md']
+        "});
+        let Validator::Dependent(dependent) = &validator else {
+            unreachable!("deserialized as the dependent validator");
+        };
+        let violations = |files: &[&str]| dependent.violations(&compiled, &changed(files)).unwrap();
+
+        let violated = violations(&["src/evaluation/dependent.rs", "docs/README.md"]);
+        assert_eq!(violated.len(), 1);
+        assert_eq!(violated[0].trigger, vec!["src/**/*.rs"]);
+        assert_eq!(violated[0].missing, vec!["Cargo.lock"]);
+
+        assert!(violations(&["Cargo.lock"]).is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+use serde_json::Value as JsonValue;
+
+use crate::configuration::compiled::Compiled;
+use crate::configuration::validate::{MilestoneChain, TheMilestone};
+use crate::evaluation::context::event_item;
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{EvaluationContext, EvaluationError, UnsupportedSnafu, ValidatorResult};
+
+const NAME: &str = "milestone";
+const SUBJECT: &str = "milestone";
+
+impl TheMilestone {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        if self.jira.is_some() {
+            return UnsupportedSnafu {
+                validator: "milestone.jira",
+            }
+            .fail();
+        }
+        let title = event_item(context.payload())
+            .and_then(|item| item.get("milestone"))
+            .and_then(|milestone| milestone.get("title"))
+            .and_then(JsonValue::as_str);
+        let vars = MessageVars::new(context, NAME);
+        let Some(title) = title else {
+            return match &self.no_empty {
+                Some(no_empty) if no_empty.enabled => Ok(ValidatorResult::fail(
+                    NAME,
+                    match &no_empty.message {
+                        Some(message) => message.render(&vars)?,
+                        None => "The milestone can't be empty".to_owned(),
+                    },
+                )),
+                // without a milestone there is nothing the other conditions could match
+                _ => Ok(ValidatorResult::pass(NAME, "No milestone is set")),
+            };
+        };
+        let failures = self.milestone.failures(compiled, &[title], &vars)?;
+        if failures.is_empty() {
+            Ok(ValidatorResult::pass(
+                NAME,
+                format!("The milestone '{title}' satisfies the milestone conditions"),
+            ))
+        } else {
+            Ok(ValidatorResult::fail(NAME, failures.join("\n")))
+        }
+    }
+}
+
+impl MilestoneChain {
+    fn failures(
+        &self,
+        compiled: &Compiled,
+        values: &[&str],
+        vars: &MessageVars<'_>,
+    ) -> Result<Vec<String>, EvaluationError> {
+        let mut failures = Vec::new();
+        if let Some(include) = &self.include {
+            failures.extend(include.failures(compiled, SUBJECT, values, vars)?);
+        }
+        if let Some(exclude) = &self.exclude {
+            failures.extend(exclude.failures(compiled, SUBJECT, values, vars)?);
+        }
+        if let Some(begins_with) = &self.begins_with {
+            failures.extend(begins_with.failures(SUBJECT, values, vars)?);
+        }
+        if let Some(ends_with) = &self.ends_with {
+            failures.extend(ends_with.failures(SUBJECT, values, vars)?);
+        }
+        for clause in self.and.iter().flatten() {
+            failures.extend(clause.failures(compiled, values, vars)?);
+        }
+        if let Some(clauses) = &self.or {
+            let mut or_failures = Vec::new();
+            for clause in clauses {
+                let clause_failures = clause.failures(compiled, values, vars)?;
+                if clause_failures.is_empty() {
+                    or_failures.clear();
+                    break;
+                }
+                or_failures.extend(clause_failures);
+            }
+            failures.extend(or_failures);
+        }
+        Ok(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::validate::Validator;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn milestone_title_is_matched() {
+        let validator: Validator = serde_yaml::from_str(indoc::indoc! {"
+            do: milestone
+            no_empty:
+              enabled: true
+            or:
+              - begins_with:
+                  match: 'v1.'
+              - must_include:
+                  regex: 'backlog'
+        "})
+        .unwrap();
+        let compiled = Compiled::of_validator(&validator);
+        let mut context = TestContext {
+            payload: json!({ "pull_request": { "milestone": { "title": "v1.4" } } }),
+            ..Default::default()
+        };
+
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Pass);
+
+        context.payload = json!({ "pull_request": { "milestone": { "title": "v2.0" } } });
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(
+            result.description,
+            "milestone does not begin with 'v1.'\nmilestone does not include 'backlog'"
+        );
+
+        context.payload = json!({ "pull_request": { "milestone": null } });
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(result.description, "The milestone can't be empty");
+    }
+}
@@ -0,0 +1,229 @@
+use serde_json::Value as JsonValue;
+
+use crate::configuration::compiled::Compiled;
+use crate::configuration::filter::ThePayload;
+use crate::configuration::pattern::Regexes;
+use crate::configuration::payload::{PayloadCheck, PayloadField, PayloadRegexClause};
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{EvaluationContext, EvaluationError, ValidatorResult};
+
+const NAME: &str = "payload";
+
+impl ThePayload {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        let payload = context.payload();
+        let vars = MessageVars::new(context, NAME);
+        let mut failures = Vec::new();
+        let fields = [
+            ("pull_request", &self.pull_request),
+            ("review", &self.review),
+            ("sender", &self.sender),
+        ];
+        for (path, field) in fields {
+            if let Some(field) = field {
+                field.failures(compiled, path, payload.get(path), &vars, &mut failures)?;
+            }
+        }
+        if failures.is_empty() {
+            Ok(ValidatorResult::pass(
+                NAME,
+                "Payload satisfies the payload conditions",
+            ))
+        } else {
+            Ok(ValidatorResult::fail(NAME, failures.join("\n")))
+        }
+    }
+}
+
+impl PayloadField {
+    fn failures(
+        &self,
+        compiled: &Compiled,
+        path: &str,
+        value: Option<&JsonValue>,
+        vars: &MessageVars<'_>,
+        failures: &mut Vec<String>,
+    ) -> Result<(), EvaluationError> {
+        match self {
+            PayloadField::Nested(fields) => {
+                for (name, field) in fields {
+                    let path = format!("{path}.{name}");
+                    field.failures(
+                        compiled,
+                        &path,
+                        value.and_then(|value| value.get(name)),
+                        vars,
+                        failures,
+                    )?;
+                }
+            }
+            PayloadField::Check(check) => check.failures(compiled, path, value, vars, failures)?,
+        }
+        Ok(())
+    }
+}
+
+impl PayloadCheck {
+    fn failures(
+        &self,
+        compiled: &Compiled,
+        path: &str,
+        value: Option<&JsonValue>,
+        vars: &MessageVars<'_>,
+        failures: &mut Vec<String>,
+    ) -> Result<(), EvaluationError> {
+        if let Some(boolean) = &self.boolean {
+            let expected = boolean.match_clause;
+            if value.and_then(JsonValue::as_bool) != Some(expected) {
+                failures.push(match &boolean.message {
+                    Some(message) => message.render(vars)?,
+                    None => format!("{path} is not {expected}"),
+                });
+            }
+        }
+        if let Some(include) = &self.include {
+            let values = include.values(value);
+            for regex in include.regexes(compiled)?.iter() {
+                if !values.iter().any(|value| regex.is_match(value)) {
+                    failures.push(match &include.message {
+                        Some(message) => message.render(vars)?,
+                        None => format!("{path} does not include '{regex}'"),
+                    });
+                }
+            }
+        }
+        if let Some(exclude) = &self.exclude {
+            let values = exclude.values(value);
+            for regex in exclude.regexes(compiled)?.iter() {
+                if values.iter().any(|value| regex.is_match(value)) {
+                    failures.push(match &exclude.message {
+                        Some(message) => message.render(vars)?,
+                        None => format!("{path} must exclude '{regex}'"),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PayloadRegexClause {
+    /// The regexes compiled for the clause with its flags.
+    fn regexes<'a>(&self, compiled: &'a Compiled) -> Result<&'a Regexes, EvaluationError> {
+        let flags = self.regex_flag.clone().unwrap_or_default();
+        compiled.regexes(self.regex.as_slice(), &flags)
+    }
+
+    /// The textual values to match, a list is matched element-wise (by `key` for objects).
+    fn values(&self, value: Option<&JsonValue>) -> Vec<String> {
+        match value {
+            Some(JsonValue::Array(items)) => items
+                .iter()
+                .filter_map(|item| match &self.key {
+                    Some(key) => item.get(key).and_then(text),
+                    None => text(item),
+                })
+                .collect(),
+            Some(value) => text(value).into_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn text(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(string) => Some(string.clone()),
+        JsonValue::Number(number) => Some(number.to_string()),
+        JsonValue::Bool(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::filter::Filter;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn nested_fields_are_matched_against_the_payload() {
+        let filter: Filter = serde_yaml::from_str(indoc::indoc! {"
+            do: payload
+            pull_request:
+              draft:
+                boolean:
+                  match: false
+              labels:
+                must_exclude:
+                  regex: 'wip'
+                  key: name
+              base:
+                ref:
+                  must_include:
+                    regex: '^main$'
+        "})
+        .unwrap();
+        let compiled = Compiled::of_filter(&filter);
+        let mut context = TestContext {
+            payload: json!({
+                "pull_request": {
+                    "draft": false,
+                    "labels": [{"name": "bug"}],
+                    "base": {"ref": "main"}
+                }
+            }),
+            ..Default::default()
+        };
+
+        let result = filter.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Pass);
+
+        context.payload = json!({
+            "pull_request": {
+                "draft": true,
+                "labels": [{"name": "bug"}, {"name": "WIP"}],
+                "base": {"ref": "main"}
+            }
+        });
+        let result = filter.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(
+            result.description,
+            "pull_request.draft is not false\npull_request.labels must exclude 'wip'"
+        );
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected_when_parsing() {
+        let err = serde_yaml::from_str::<Filter>(indoc::indoc! {"
+            do: payload
+            pull_request:
+              drafted:
+                boolean:
+                  match: false
+        "})
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("The field 'pull_request.drafted' does not exist in the event payload"));
+
+        let err = serde_yaml::from_str::<Filter>(indoc::indoc! {"
+            do: payload
+            pull_request:
+              title:
+                boolean:
+                  match: true
+        "})
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("The field 'pull_request.title' does not support 'boolean'"));
+    }
+}
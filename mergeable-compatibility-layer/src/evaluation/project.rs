@@ -0,0 +1,72 @@
+use snafu::ResultExt;
+
+use crate::configuration::compiled::Compiled;
+use crate::configuration::validate::TheProject;
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{ContextSnafu, EvaluationContext, EvaluationError, ValidatorResult};
+
+const NAME: &str = "project";
+
+impl TheProject {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        let Some(include) = &self.include else {
+            return Ok(ValidatorResult::pass(NAME, "No project conditions are set"));
+        };
+        let projects = context
+            .projects()
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ContextSnafu { what: "projects" })?;
+        let projects: Vec<_> = projects.iter().map(String::as_str).collect();
+        let vars = MessageVars::new(context, NAME);
+        let failures = include.failures(compiled, "project", &projects, &vars)?;
+        if failures.is_empty() {
+            Ok(ValidatorResult::pass(
+                NAME,
+                "The projects satisfy the project conditions",
+            ))
+        } else {
+            Ok(ValidatorResult::fail(NAME, failures.join("\n")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::validate::Validator;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn one_of_the_projects_has_to_match() {
+        let validator: Validator = serde_yaml::from_str(indoc::indoc! {"
+            do: project
+            must_include:
+              regex: '^roadmap'
+              message: 'Add #{{number}} by {{author}} to the roadmap'
+        "})
+        .unwrap();
+        let compiled = Compiled::of_validator(&validator);
+        let mut context = TestContext {
+            author: "octocat".into(),
+            payload: json!({ "pull_request": { "number": 42 } }),
+            projects: vec!["Triage".into(), "Roadmap 2024".into()],
+            ..Default::default()
+        };
+
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Pass);
+
+        context.projects.clear();
+        let result = validator.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(result.description, "Add #42 by octocat to the roadmap");
+    }
+}
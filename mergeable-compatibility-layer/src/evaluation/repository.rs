@@ -0,0 +1,104 @@
+use snafu::ResultExt;
+
+use crate::configuration::compiled::Compiled;
+use crate::configuration::filter::TheRepository;
+use crate::evaluation::template::MessageVars;
+use crate::evaluation::{ContextSnafu, EvaluationContext, EvaluationError, ValidatorResult};
+
+const NAME: &str = "repository";
+
+impl TheRepository {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        let vars = MessageVars::new(context, NAME);
+        let mut failures = Vec::new();
+        if let Some(visibility) = self.visibility {
+            let actual = context.repository_visibility();
+            if actual != visibility {
+                failures.push(format!(
+                    "Repository visibility is '{}' instead of '{}'",
+                    actual.as_str(),
+                    visibility.as_str()
+                ));
+            }
+        }
+        if let Some(name) = &self.name {
+            failures.extend(name.failures(
+                compiled,
+                "repository name",
+                &[context.repository_name()],
+                &vars,
+            )?);
+        }
+        // topics might need an API call, so they are only checked when still relevant
+        if let (true, Some(topics)) = (failures.is_empty(), &self.topics) {
+            let actual = context
+                .repository_topics()
+                .await
+                .map_err(|err| Box::new(err) as _)
+                .context(ContextSnafu {
+                    what: "repository topics",
+                })?;
+            let actual: Vec<_> = actual.iter().map(String::as_str).collect();
+            failures.extend(topics.failures(compiled, "repository topics", &actual, &vars)?);
+        }
+        if failures.is_empty() {
+            Ok(ValidatorResult::pass(
+                NAME,
+                "Repository satisfies the repository conditions",
+            ))
+        } else {
+            Ok(ValidatorResult::fail(NAME, failures.join("\n")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::compiled::Compiled;
+    use crate::configuration::filter::Filter;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::Status;
+
+    #[tokio::test]
+    async fn public_service_repositories_only() {
+        let filter: Filter = serde_yaml::from_str(indoc::indoc! {"
+            do: repository
+            visibility: public
+            name:
+              must_exclude:
+                regex: '-archive$'
+            topics:
+              must_include:
+                regex: '^service$'
+        "})
+        .unwrap();
+        let compiled = Compiled::of_filter(&filter);
+        let mut context = TestContext {
+            repository_name: "billing".into(),
+            repository_topics: vec!["rust".into(), "service".into()],
+            ..Default::default()
+        };
+
+        let result = filter.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Pass);
+
+        context.repository_topics = vec!["library".into()];
+        let result = filter.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(result.status, Status::Fail);
+        assert_eq!(
+            result.description,
+            "repository topics does not include '^service$'"
+        );
+
+        context.repository_private = true;
+        let result = filter.evaluate(&context, &compiled).await.unwrap();
+        assert_eq!(
+            result.description,
+            "Repository visibility is 'private' instead of 'public'"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+
+use crate::configuration::actions::Action;
+use crate::configuration::compiled::Compiled;
+use crate::configuration::validate::Validator;
+use crate::configuration::{Configuration, Rule};
+use crate::evaluation::{outcome, EvaluationContext, Status, ValidatorResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleStatus {
+    Pass,
+    Fail,
+    Error,
+    /// One of the filters didn't match, so the rule doesn't apply to the event.
+    Skipped,
+}
+
+impl RuleStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleStatus::Pass => "pass",
+            RuleStatus::Fail => "fail",
+            RuleStatus::Error => "error",
+            RuleStatus::Skipped => "skipped",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleResult {
+    pub name: Option<String>,
+    pub status: RuleStatus,
+    pub filters: Vec<ValidatorResult>,
+    pub validators: Vec<ValidatorResult>,
+}
+
+impl RuleResult {
+    /// The pull requests and issues found by scheduled validators, in ascending order.
+    pub fn items(&self) -> Vec<u64> {
+        let mut items: Vec<u64> = self
+            .validators
+            .iter()
+            .flat_map(|validator| validator.items.iter().copied())
+            .collect();
+        items.sort_unstable();
+        items.dedup();
+        items
+    }
+}
+
+impl Configuration {
+    /// Identifies the rule across events, by its name or else its position, which the lint warns
+    /// about for the comments and check runs of unnamed rules.
+    pub fn rule_key(&self, rule: &Rule) -> String {
+        if let Some(name) = &rule.name {
+            return name.clone();
+        }
+        let position = self
+            .mergeable
+            .iter()
+            .position(|other| std::ptr::eq(other, rule))
+            .unwrap_or_default();
+        format!("rule #{}", position + 1)
+    }
+}
+
+impl Rule {
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The comma separated events of `when`, which may use `*` for any action.
+    pub fn events(&self) -> impl Iterator<Item = &str> {
+        self.when
+            .iter()
+            .flat_map(|when| when.split(',').map(str::trim))
+    }
+
+    pub fn is_triggered_by(&self, event: &str) -> bool {
+        self.events().any(|pattern| event_matches(pattern, event))
+    }
+
+    /// The actions to run for the outcome of the rule, none if it was skipped.
+    pub fn actions(&self, status: RuleStatus) -> Vec<&Action> {
+        match status {
+            RuleStatus::Pass => self.pass.iter().flatten().map(|pass| &pass.0).collect(),
+            RuleStatus::Fail => self.fail.iter().flatten().map(|fail| &fail.0).collect(),
+            RuleStatus::Error => self.error.iter().flatten().map(|error| &error.0).collect(),
+            RuleStatus::Skipped => Vec::new(),
+        }
+    }
+
+    /// Whether the time constraints of the rule allow it to run at the given point in time.
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.validate
+            .iter()
+            .flatten()
+            .all(|validator| match validator {
+                Validator::Stale(stale) => stale.is_active_at(now),
+                _ => true,
+            })
+    }
+
+    /// Evaluates the rule with the regexes and globs compiled for it, see
+    /// [`CompiledRule::evaluate`](crate::configuration::compiled::CompiledRule::evaluate).
+    pub(crate) async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+        compiled: &Compiled,
+    ) -> RuleResult {
+        let mut result = RuleResult {
+            name: self.name.clone(),
+            status: RuleStatus::Pass,
+            filters: Vec::new(),
+            validators: Vec::new(),
+        };
+        for filter in self.filter.iter().flatten() {
+            let filtered = outcome(filter.name(), filter.evaluate(context, compiled).await);
+            let status = filtered.status;
+            result.filters.push(filtered);
+            match status {
+                Status::Pass => {}
+                Status::Fail => {
+                    result.status = RuleStatus::Skipped;
+                    return result;
+                }
+                Status::Error => {
+                    result.status = RuleStatus::Error;
+                    return result;
+                }
+            }
+        }
+        for validator in self.validate.iter().flatten() {
+            let validated = outcome(
+                validator.name(),
+                validator.evaluate(context, compiled).await,
+            );
+            result.status = match (result.status, validated.status) {
+                (RuleStatus::Error, _) | (_, Status::Error) => RuleStatus::Error,
+                (RuleStatus::Fail, _) | (_, Status::Fail) => RuleStatus::Fail,
+                (status, Status::Pass) => status,
+            };
+            result.validators.push(validated);
+        }
+        result
+    }
+}
+
+/// Whether the event, e.g. `pull_request.opened`, matches the pattern, which may use `*` for any
+/// action.
+pub(crate) fn event_matches(pattern: &str, event: &str) -> bool {
+    match pattern.strip_suffix(".*") {
+        Some(kind) => event.split_once('.').map(|(event_kind, _)| event_kind) == Some(kind),
+        None => pattern == event,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::Configuration;
+    use crate::evaluation::context::TestContext;
+    use crate::evaluation::{ChangedFile, FileStatus, RuleStatus, Status};
+
+    #[tokio::test]
+    async fn rules_are_selected_by_event_and_filtered() {
+        let config = Configuration::from_yaml(indoc::indoc! {"
+            version: 2
+            mergeable:
+              - when: pull_request.opened, pull_request.synchronize
+                name: lockfile
+                filter:
+                  - do: author
+                    must_exclude:
+                      regex: 'dependabot'
+                validate:
+                  - do: dependent
+                    files: ['Cargo.toml', 'Cargo.lock']
+                  - do: headRef
+                    must_include:
+                      regex: '^feature/'
+              - when: schedule.repository
+                name: stale
+                validate:
+                  - do: stale
+        "})
+        .unwrap()
+        .compile();
+        let mut context = TestContext {
+            author: "octocat".into(),
+            changed_files: vec![ChangedFile {
+                filename: "Cargo.toml".into(),
+                status: FileStatus::Modified,
+            }],
+            ..Default::default()
+        };
+
+        let rules: Vec<_> = config.rules_for("pull_request.synchronize").collect();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].rule().name(), Some("lockfile"));
+        assert_eq!(config.rules_for("pull_request.closed").count(), 0);
+
+        let result = rules[0].evaluate(&context).await;
+        assert_eq!(result.status, RuleStatus::Error);
+        assert_eq!(result.validators[0].status, Status::Fail);
+        assert_eq!(result.validators[1].status, Status::Error);
+
+        context.author = "dependabot[bot]".into();
+        let result = rules[0].evaluate(&context).await;
+        assert_eq!(result.status, RuleStatus::Skipped);
+        assert!(result.validators.is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+use chrono::{DateTime, Datelike, TimeDelta, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use snafu::ResultExt;
+
+use crate::configuration::validate::{ResourceType, ResourceTypes, TheStale, TimeConstraint};
+use crate::evaluation::{ContextSnafu, EvaluationContext, EvaluationError, ValidatorResult};
+
+const NAME: &str = "stale";
+const DEFAULT_DAYS: u32 = 20;
+
+impl TheStale {
+    pub async fn evaluate<C: EvaluationContext>(
+        &self,
+        context: &C,
+    ) -> Result<ValidatorResult, EvaluationError> {
+        let qualifiers = self.qualifiers(context.now());
+        let items = context
+            .search_items(&qualifiers)
+            .await
+            .map_err(|err| Box::new(err) as _)
+            .context(ContextSnafu {
+                what: "stale items",
+            })?;
+        if items.is_empty() {
+            return Ok(ValidatorResult::pass(NAME, "No stale items found"));
+        }
+        let numbers: Vec<_> = items
+            .iter()
+            .map(|item| format!("#{}", item.number))
+            .collect();
+        // like in mergeable, the validator passes and the `pass` actions apply to the items
+        Ok(ValidatorResult::pass(
+            NAME,
+            format!(
+                "{count} items have been inactive for {days} days: {numbers}",
+                count = items.len(),
+                days = self.days.unwrap_or(DEFAULT_DAYS),
+                numbers = numbers.join(", "),
+            ),
+        )
+        .with_items(items.iter().map(|item| item.number).collect()))
+    }
+
+    /// The search qualifiers, besides the repository, for open items without activity.
+    fn qualifiers(&self, now: DateTime<Utc>) -> String {
+        let days = self.days.unwrap_or(DEFAULT_DAYS);
+        let updated_before = now - TimeDelta::days(days.into());
+        let mut qualifiers = vec![
+            "is:open".to_owned(),
+            format!("updated:<{}", updated_before.format("%Y-%m-%d")),
+        ];
+        let (mut pull_requests, mut issues) = (false, false);
+        let types = self.r#type.iter().flat_map(|types| types.as_slice());
+        for item_type in types.flat_map(ResourceTypes::types) {
+            match item_type {
+                ResourceType::PullRequest => pull_requests = true,
+                ResourceType::Issues => issues = true,
+            }
+        }
+        match (pull_requests, issues) {
+            (true, false) => qualifiers.push("type:pr".to_owned()),
+            (false, true) => qualifiers.push("type:issue".to_owned()),
+            _ => {}
+        }
+        if self.ignore_drafts == Some(true) {
+            qualifiers.push("-is:draft".to_owned());
+        }
+        if self.ignore_milestones == Some(true) {
+            qualifiers.push("no:milestone".to_owned());
+        }
+        if self.ignore_projects == Some(true) {
+            qualifiers.push("no:project".to_owned());
+        }
+        if let Some(label) = &self.label {
+            // a comma separated list of labels matches any of them
+            if let Some(labels) = label.r#match.as_ref().filter(|labels| !labels.is_empty()) {
+                let labels: Vec<_> = labels.iter().map(|label| format!("\"{label}\"")).collect();
+                qualifiers.push(format!("label:{}", labels.join(",")));
+            }
+            for ignored in label.ignore.iter().flatten() {
+                qualifiers.push(format!("-label:\"{ignored}\""));
+            }
+        }
+        qualifiers.join(" ")
+    }
+
+    /// Whether the validator is allowed to run at the given point in time.
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.time_constraint
+            .as_ref()
+            .is_none_or(|constraint| constraint.admits(now))
+    }
+}
+
+impl TimeConstraint {
+    pub fn admits(&self, now: DateTime<Utc>) -> bool {
+        let time_zone = self.time_zone.map_or(Tz::UTC, |time_zone| time_zone.0);
+        let now = now.with_timezone(&time_zone);
+        if let Some(hours) = self.hours_between {
+            if !(hours.from..=hours.to).contains(&now.hour()) {
+                return false;
+            }
+        }
+        if let Some(days) = &self.days_of_week {
+            if !days.iter().any(|day| Weekday::from(*day) == now.weekday()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use crate::configuration::validate::TheStale;
+
+    fn stale(yaml: &str) -> TheStale {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn search_qualifiers_follow_the_settings() {
+        let now: DateTime<Utc> = "2024-03-21T10:00:00Z".parse().unwrap();
+        let stale = stale(indoc::indoc! {"
+            days: 20
+            type: pull_request
+            ignore_drafts: true
+            ignore_projects: true
+            label:
+              match: [needs-review, blocked]
+              ignore: [pinned]
+        "});
+
+        assert_eq!(
+            stale.qualifiers(now),
+            r#"is:open updated:<2024-03-01 type:pr -is:draft no:project label:"needs-review","blocked" -label:"pinned""#
+        );
+
+        let stale = super::TheStale {
+            r#type: serde_yaml::from_str("pull_request, issues").unwrap(),
+            ..stale
+        };
+        assert_eq!(
+            stale.qualifiers(now),
+            r#"is:open updated:<2024-03-01 -is:draft no:project label:"needs-review","blocked" -label:"pinned""#
+        );
+    }
+
+    #[test]
+    fn time_constraints_respect_the_time_zone() {
+        let stale = stale(indoc::indoc! {r#"
+            time_constraint:
+              time_zone: "America/Los_Angeles"
+              hours_between: ["9", "17"]
+              days_of_week: ["Mon", "Tue", "Wed", "Thu", "Fri"]
+        "#});
+
+        // Thursday 10:00 in Los Angeles
+        let now: DateTime<Utc> = "2024-03-21T17:00:00Z".parse().unwrap();
+        assert!(stale.is_active_at(now));
+        // Thursday 03:00 in Los Angeles
+        let now: DateTime<Utc> = "2024-03-21T10:00:00Z".parse().unwrap();
+        assert!(!stale.is_active_at(now));
+        // Saturday 10:00 in Los Angeles
+        let now: DateTime<Utc> = "2024-03-23T17:00:00Z".parse().unwrap();
+        assert!(!stale.is_active_at(now));
+
+        for hours in ["['9']", "['9', '24']", "['nine', '17']"] {
+            let yaml = format!("time_constraint: {{ hours_between: {hours} }}");
+            assert!(serde_yaml::from_str::<TheStale>(&yaml).is_err(), "{hours}");
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::sync::LazyLock;
+
+use handlebars::{handlebars_helper, Handlebars};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use snafu::ResultExt;
+
+use crate::configuration::basics::MessageClause;
+use crate::configuration::template::Template;
+use crate::evaluation::context::event_item;
+use crate::evaluation::{EvaluationContext, EvaluationError, RenderSnafu, RuleResult};
+
+handlebars_helper!(status_icon: |status: str| match status {
+    "pass" => "✔️",
+    "fail" => "❌",
+    "error" => "⚠️",
+    _ => "ℹ️",
+});
+handlebars_helper!(break_lines: |text: str| text.replace('\n', "<br>"));
+handlebars_helper!(to_upper_case: |text: str| text.to_uppercase());
+
+/// The helpers mergeable offers to its templates, rendering markdown instead of HTML.
+static REGISTRY: LazyLock<Handlebars<'static>> = LazyLock::new(|| {
+    let mut registry = Handlebars::new();
+    registry.register_escape_fn(handlebars::no_escape);
+    registry.register_helper("statusIcon", Box::new(status_icon));
+    registry.register_helper("breaklines", Box::new(break_lines));
+    registry.register_helper("toUpperCase", Box::new(to_upper_case));
+    registry
+});
+
+impl Template {
+    pub fn render(&self, data: &impl Serialize) -> Result<String, EvaluationError> {
+        render(self.as_str(), data)
+    }
+}
+
+/// Renders a template of this crate, which unlike configured ones hasn't been checked yet.
+pub(crate) fn render(source: &str, data: &impl Serialize) -> Result<String, EvaluationError> {
+    REGISTRY
+        .render_template(source, data)
+        .map_err(Box::new)
+        .context(RenderSnafu)
+}
+
+impl MessageClause {
+    pub(crate) fn render(&self, vars: &MessageVars<'_>) -> Result<String, EvaluationError> {
+        self.0.render(vars)
+    }
+}
+
+/// What is known about the event independent of any validator.
+#[derive(Debug, Default, Serialize)]
+pub struct EventVars<'a> {
+    pub author: &'a str,
+    pub title: Option<&'a str>,
+    pub number: Option<u64>,
+    pub repository: &'a str,
+}
+
+impl<'a> EventVars<'a> {
+    pub fn new<C: EvaluationContext>(context: &'a C) -> Self {
+        let item = event_item(context.payload());
+        Self {
+            author: context.author(),
+            title: item
+                .and_then(|item| item.get("title"))
+                .and_then(JsonValue::as_str),
+            number: item
+                .and_then(|item| item.get("number"))
+                .and_then(JsonValue::as_u64),
+            repository: context.repository_name(),
+        }
+    }
+}
+
+/// The variables available to the custom `message` of a validator.
+#[derive(Debug, Default, Serialize)]
+pub struct MessageVars<'a> {
+    #[serde(flatten)]
+    pub event: EventVars<'a>,
+    pub validator: &'static str,
+    /// The files the validator looked at, if it is about files at all.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<&'a str>,
+}
+
+impl<'a> MessageVars<'a> {
+    pub fn new<C: EvaluationContext>(context: &'a C, validator: &'static str) -> Self {
+        Self {
+            event: EventVars::new(context),
+            validator,
+            files: Vec::new(),
+        }
+    }
+}
+
+/// The variables available to comments and checks, following mergeable's naming.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleVars<'a> {
+    #[serde(flatten)]
+    pub event: EventVars<'a>,
+    pub name: Option<&'a str>,
+    pub validation_status: &'static str,
+    pub validation_suites: Vec<SuiteVars<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuiteVars<'a> {
+    pub name: &'static str,
+    pub status: &'static str,
+    pub description: &'a str,
+    /// Mergeable reports several validations per suite, here every suite is a single one.
+    pub validations: [ValidationVars<'a>; 1],
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidationVars<'a> {
+    pub status: &'static str,
+    pub description: &'a str,
+}
+
+impl RuleResult {
+    pub fn vars<'a>(&'a self, event: EventVars<'a>) -> RuleVars<'a> {
+        RuleVars {
+            event,
+            name: self.name.as_deref(),
+            validation_status: self.status.as_str(),
+            validation_suites: self
+                .validators
+                .iter()
+                .map(|result| SuiteVars {
+                    name: result.name,
+                    status: result.status.as_str(),
+                    description: &result.description,
+                    validations: [ValidationVars {
+                        status: result.status.as_str(),
+                        description: &result.description,
+                    }],
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configuration::template::Template;
+    use crate::evaluation::template::EventVars;
+    use crate::evaluation::{RuleResult, RuleStatus, ValidatorResult};
+
+    #[test]
+    fn rule_results_render_like_mergeable() {
+        let template = Template::try_from(
+            indoc::indoc! {"
+                {{statusIcon validationStatus}} {{title}} by @{{author}}
+                {{#each validationSuites}}
+                - {{name}}: {{#each validations}}{{breaklines description}}{{/each}}
+                {{/each}}"}
+            .to_owned(),
+        )
+        .unwrap();
+        let result = RuleResult {
+            name: Some("release".into()),
+            status: RuleStatus::Fail,
+            filters: Vec::new(),
+            validators: vec![
+                ValidatorResult::pass("author", "octocat satisfies the author conditions"),
+                ValidatorResult::fail("milestone", "not a release\nnot planned"),
+            ],
+        };
+        let event = EventVars {
+            author: "octocat",
+            title: Some("Release <1.0>"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            template.render(&result.vars(event)).unwrap(),
+            indoc::indoc! {"
+                ❌ Release <1.0> by @octocat
+                - author: octocat satisfies the author conditions
+                - milestone: not a release<br>not planned
+            "}
+        );
+        assert!(Template::try_from("{{#each files}}".to_owned()).is_err());
+    }
+}
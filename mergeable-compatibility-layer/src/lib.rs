@@ -1 +1,3 @@
+pub mod action;
 pub mod configuration;
+pub mod evaluation;
@@ -5,6 +5,7 @@ use jsonwebtoken::EncodingKey;
 use octocrab::models::AppId;
 use orion::{errors::UnknownCryptoError, hazardous::mac::hmac::sha256::SecretKey};
 use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
 use thiserror::Error;
 
 pub fn load_github_app_config() -> Result<
@@ -12,6 +13,7 @@ pub fn load_github_app_config() -> Result<
         GitHubAppConfiguration,
         WebhookEndpointConfiguration,
         InternalEndpointConfiguration,
+        ScheduleConfiguration,
     ),
     ConfigurationError,
 > {
@@ -24,6 +26,7 @@ pub fn load_github_app_config() -> Result<
         webhook_addr: Option<SocketAddr>,
         webhook_endpoint: Option<String>,
         internal_addr: Option<SocketAddr>,
+        schedule_interval: Option<u64>,
     }
 
     let raw_config: ApplicationRawConfig = {
@@ -56,7 +59,15 @@ pub fn load_github_app_config() -> Result<
             .internal_addr
             .unwrap_or(SocketAddr::new(IpAddr::from([0, 0, 0, 0]), 3001)),
     };
-    Ok((app_config, public_ep_config, internal_ep_config))
+    let schedule_config = ScheduleConfiguration {
+        interval: Duration::from_secs(raw_config.schedule_interval.unwrap_or(60 * 60)),
+    };
+    Ok((
+        app_config,
+        public_ep_config,
+        internal_ep_config,
+        schedule_config,
+    ))
 }
 
 pub struct GitHubAppConfiguration {
@@ -77,6 +88,12 @@ pub struct InternalEndpointConfiguration {
     pub addr: SocketAddr,
 }
 
+/// How often the `schedule.repository` rules are evaluated.
+#[derive(Debug)]
+pub struct ScheduleConfiguration {
+    pub interval: Duration,
+}
+
 #[derive(Debug, Error)]
 pub enum ConfigurationError {
     #[error("Cannot read from environment configuration")]
@@ -1,26 +1,44 @@
 pub mod config;
 pub mod routes;
 
-use crate::config::{InternalEndpointConfiguration, WebhookEndpointConfiguration};
+use crate::config::{
+    InternalEndpointConfiguration, ScheduleConfiguration, WebhookEndpointConfiguration,
+};
 use axum::{middleware::from_fn, Router};
 use config::GitHubAppConfiguration;
 use github_event_handler::authentication::GitHubAppAuthenticator;
+use github_event_handler::configuration::ConfigurationCache;
+use github_event_handler::membership::MembershipCache;
+use github_event_handler::schedule::schedule;
+use hyper::Uri;
+use jsonwebtoken::EncodingKey;
+use octocrab::models::AppId;
 pub use routes::metrics::track_metrics;
 use tokio::net::TcpListener;
 use tracing::instrument;
 
-#[instrument(skip(app_config))]
+/// The caches shared by the event handler and the scheduler, so a push invalidates both.
+#[derive(Debug, Default, Clone)]
+pub struct Caches {
+    pub memberships: MembershipCache,
+    pub configurations: ConfigurationCache,
+}
+
+#[instrument(skip(app_config, caches))]
 pub async fn public_app<C: GitHubAppAuthenticator>(
     app_config: GitHubAppConfiguration,
     endpoint_config: WebhookEndpointConfiguration,
+    caches: Caches,
 ) -> Result<(), Box<dyn std::error::Error>>
 where
     C::Error: 'static,
     C::Next: 'static,
 {
+    let event_handler =
+        routes::event_handler::router::<C>(app_config, &endpoint_config.path, caches).await?;
     let routes = Router::new()
         .merge(routes::ui::router())
-        .merge(routes::event_handler::router::<C>(app_config, &endpoint_config.path).await?)
+        .merge(event_handler)
         .route_layer(from_fn(track_metrics));
 
     let listener = {
@@ -45,3 +63,29 @@ pub async fn internal_app(
 
     Ok(axum::serve(listener, routes).await?)
 }
+
+#[instrument(skip(app_key, caches))]
+pub async fn scheduler_app<C: GitHubAppAuthenticator>(
+    uri: Uri,
+    app_id: AppId,
+    app_key: EncodingKey,
+    schedule_config: ScheduleConfiguration,
+    caches: Caches,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    C::Error: 'static,
+{
+    let client = C::authenticate_app(uri, app_id, app_key)?;
+    let Caches {
+        memberships,
+        configurations,
+    } = caches;
+    schedule(
+        client,
+        schedule_config.interval,
+        memberships,
+        configurations,
+    )
+    .await;
+    Ok(())
+}
@@ -10,11 +10,19 @@ use server::config::{load_github_app_config, GitHubAppConfiguration};
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     setup_tracing()?;
     setup_crypto()?;
-    let (app_config, public_ep, internal_ep) = load_github_app_config()?; //.unwrap_or(create_dummy_config());
+    let (app_config, public_ep, internal_ep, schedule) = load_github_app_config()?; //.unwrap_or(create_dummy_config());
+    let (uri, app_id, app_key) = (
+        app_config.uri.clone(),
+        app_config.app_identifier,
+        app_config.app_key.clone(),
+    );
+
+    let caches = server::Caches::default();
 
     tokio::try_join!(
-        server::public_app::<Octocrab>(app_config, public_ep),
-        server::internal_app(internal_ep)
+        server::public_app::<Octocrab>(app_config, public_ep, caches.clone()),
+        server::internal_app(internal_ep),
+        server::scheduler_app::<Octocrab>(uri, app_id, app_key, schedule, caches)
     )?;
     Ok(())
 }
@@ -2,13 +2,16 @@ use std::sync::Arc;
 
 use self::extractors::GitHubEvent;
 use crate::config::GitHubAppConfiguration;
+use crate::Caches;
 use axum::http::Uri;
 use axum::{extract::State, response::IntoResponse, routing::any, Router};
 use axum_core::extract::FromRef;
 use github_event_handler::authentication::{
     AuthenticatedClient, GitHubAppAuthenticator, InstallationAuthenticator,
 };
+use github_event_handler::configuration::ConfigurationCache;
 use github_event_handler::handle::{handle_event, HandleEventError};
+use github_event_handler::membership::MembershipCache;
 use hyper::StatusCode;
 use jsonwebtoken::EncodingKey;
 use octocrab::models::AppId;
@@ -19,6 +22,7 @@ mod extractors;
 pub async fn router<C: GitHubAppAuthenticator>(
     config: GitHubAppConfiguration,
     path: &str,
+    caches: Caches,
 ) -> Result<Router, Box<dyn std::error::Error>>
 where
     C::Error: 'static,
@@ -28,6 +32,8 @@ where
     let signature_config = ConfigState {
         webhook_secret: config.webhook_secret.into(),
         client,
+        memberships: caches.memberships,
+        configurations: caches.configurations,
     };
     Ok(Router::new().route(path, any(handle_github_event).with_state(signature_config)))
 }
@@ -36,6 +42,8 @@ where
 struct ConfigState<C: InstallationAuthenticator + Clone> {
     webhook_secret: Arc<SecretKey>,
     client: AuthenticatedClient<C>,
+    memberships: MembershipCache,
+    configurations: ConfigurationCache,
 }
 
 impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for Arc<SecretKey> {
@@ -50,6 +58,18 @@ impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for Authentic
     }
 }
 
+impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for MembershipCache {
+    fn from_ref(input: &ConfigState<C>) -> Self {
+        input.memberships.clone()
+    }
+}
+
+impl<C: InstallationAuthenticator + Clone> FromRef<ConfigState<C>> for ConfigurationCache {
+    fn from_ref(input: &ConfigState<C>) -> Self {
+        input.configurations.clone()
+    }
+}
+
 async fn authenticate_app<C: GitHubAppAuthenticator>(
     github_uri: Uri,
     app_id: AppId,
@@ -61,6 +81,8 @@ async fn authenticate_app<C: GitHubAppAuthenticator>(
 
 async fn handle_github_event<C: InstallationAuthenticator + Clone>(
     State(AuthenticatedClient { client }): State<AuthenticatedClient<C>>,
+    State(memberships): State<MembershipCache>,
+    State(configurations): State<ConfigurationCache>,
     GitHubEvent(event): GitHubEvent,
 ) -> impl IntoResponse {
     let handle_err = |err: HandleEventError| {
@@ -88,7 +110,7 @@ async fn handle_github_event<C: InstallationAuthenticator + Clone>(
                 .into_response(),
         }
     };
-    match handle_event(client, event).await {
+    match handle_event(client, &memberships, &configurations, event).await {
         Ok(Some(res)) => (StatusCode::OK, res).into_response(),
         Ok(None) => (StatusCode::NO_CONTENT).into_response(),
         Err(err) => handle_err(err).into_response(),
@@ -97,14 +119,18 @@ async fn handle_github_event<C: InstallationAuthenticator + Clone>(
 
 #[cfg(test)]
 mod test {
-    use super::{GitHubAppAuthenticator, InstallationAuthenticator};
+    use super::{Caches, GitHubAppAuthenticator, InstallationAuthenticator};
     use crate::config::GitHubAppConfiguration;
     use axum::{body::Body, http::Request};
     use futures_util::never::Never;
-    use github_event_handler::api::GitHubApi;
+    use github_event_handler::api::{GitHubApi, MergeOutcome, RepositoryFile};
     use http_body_util::BodyExt;
     use hyper::{StatusCode, Uri};
-    use octocrab::models::Repository;
+    use mergeable_compatibility_layer::action::{CheckRun, MergeCommit, StateReason};
+    use octocrab::models::issues::{Comment, Issue};
+    use octocrab::models::pulls::PullRequest;
+    use octocrab::models::repos::DiffEntry;
+    use octocrab::models::{CheckRunId, CommentId, Repository};
     use orion::hazardous::mac::hmac::sha256::{HmacSha256, SecretKey};
     use rsa::RsaPublicKey;
     use serde_json::json;
@@ -115,15 +141,235 @@ mod test {
     struct TestClient;
 
     #[derive(Debug, Error)]
-    enum TestError {}
+    enum TestError {
+        #[error("{0} is not part of the fixtures")]
+        Missing(&'static str),
+    }
 
+    #[derive(Clone)]
     struct NoOpApi;
 
     impl GitHubApi for NoOpApi {
         #[allow(refining_impl_trait)]
-        async fn create_commit_status(&self, _: &Repository, _: &str) -> Result<(), TestError> {
+        async fn create_check_run(
+            &self,
+            _: &Repository,
+            _: &str,
+            _: &str,
+            _: &CheckRun,
+        ) -> Result<CheckRunId, TestError> {
+            Ok(CheckRunId(1))
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_pull_request_files(
+            &self,
+            _: &Repository,
+            _: u64,
+        ) -> Result<Vec<DiffEntry>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_repository_topics(&self, _: &Repository) -> Result<Vec<String>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_team_members(&self, _: &str, _: &str) -> Result<Vec<String>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_item_projects(
+            &self,
+            _: &Repository,
+            _: u64,
+        ) -> Result<Vec<String>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn get_pull_request(&self, _: &Repository, _: u64) -> Result<PullRequest, TestError> {
+            Err(TestError::Missing("the pull request"))
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_commit_pull_requests(
+            &self,
+            _: &Repository,
+            _: &str,
+        ) -> Result<Vec<PullRequest>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn merge_pull_request(
+            &self,
+            _: &Repository,
+            _: u64,
+            _: &str,
+            _: &MergeCommit,
+        ) -> Result<MergeOutcome, TestError> {
+            Ok(MergeOutcome::Merged)
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn bot_login(&self) -> Result<String, TestError> {
+            Ok("mergeable[bot]".to_owned())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_issue_comments(
+            &self,
+            _: &Repository,
+            _: u64,
+        ) -> Result<Vec<Comment>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn create_issue_comment(
+            &self,
+            _: &Repository,
+            _: u64,
+            _: &str,
+        ) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn update_issue_comment(
+            &self,
+            _: &Repository,
+            _: CommentId,
+            _: &str,
+        ) -> Result<(), TestError> {
             Ok(())
         }
+
+        #[allow(refining_impl_trait)]
+        async fn delete_issue_comment(
+            &self,
+            _: &Repository,
+            _: CommentId,
+        ) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn get_issue(&self, _: &Repository, _: u64) -> Result<Issue, TestError> {
+            Err(TestError::Missing("the issue"))
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn close_issue(
+            &self,
+            _: &Repository,
+            _: u64,
+            _: StateReason,
+        ) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn request_reviewers(
+            &self,
+            _: &Repository,
+            _: u64,
+            _: &[String],
+            _: &[String],
+        ) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn add_assignees(
+            &self,
+            _: &Repository,
+            _: u64,
+            _: &[String],
+        ) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_issue_labels(
+            &self,
+            _: &Repository,
+            _: u64,
+        ) -> Result<Vec<String>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_repository_labels(&self, _: &Repository) -> Result<Vec<String>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn create_label(&self, _: &Repository, _: &str, _: &str) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn add_issue_labels(
+            &self,
+            _: &Repository,
+            _: u64,
+            _: &[String],
+        ) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn remove_issue_label(
+            &self,
+            _: &Repository,
+            _: u64,
+            _: &str,
+        ) -> Result<(), TestError> {
+            Ok(())
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn fetch_file(
+            &self,
+            _: &Repository,
+            _: &str,
+        ) -> Result<Option<RepositoryFile>, TestError> {
+            Ok(None)
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn fetch_repository_file(
+            &self,
+            _: &str,
+            _: &str,
+            _: &str,
+        ) -> Result<Option<RepositoryFile>, TestError> {
+            Ok(None)
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn fetch_file_at(
+            &self,
+            _: &Repository,
+            _: &str,
+            _: &str,
+        ) -> Result<Option<RepositoryFile>, TestError> {
+            Ok(None)
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn search_issues(&self, _: &str) -> Result<Vec<Issue>, TestError> {
+            Ok(vec![])
+        }
+
+        #[allow(refining_impl_trait)]
+        async fn list_installation_repositories(&self) -> Result<Vec<Repository>, TestError> {
+            Ok(vec![])
+        }
     }
 
     impl GitHubAppAuthenticator for TestClient {
@@ -147,13 +393,19 @@ mod test {
         ) -> Result<impl GitHubApi, Self::Error> {
             Ok(NoOpApi)
         }
+
+        async fn list_installations(
+            &self,
+        ) -> Result<Vec<octocrab::models::InstallationId>, Self::Error> {
+            Ok(vec![])
+        }
     }
 
     #[tracing_test::traced_test]
     #[tokio::test]
     async fn test_happy_path() {
         let (config, _, secret) = create_test_config();
-        let app = super::router::<TestClient>(config, "/event_handler")
+        let app = super::router::<TestClient>(config, "/event_handler", Caches::default())
             .await
             .unwrap();
 
@@ -187,7 +439,7 @@ mod test {
     #[tokio::test]
     async fn test_missing_signature() {
         let (config, _, _) = create_test_config();
-        let app = super::router::<TestClient>(config, "/event_handler")
+        let app = super::router::<TestClient>(config, "/event_handler", Caches::default())
             .await
             .unwrap();
 
@@ -206,7 +458,7 @@ mod test {
     #[tokio::test]
     async fn test_wrong_signature() {
         let (config, _, _) = create_test_config();
-        let app = super::router::<TestClient>(config, "/event_handler")
+        let app = super::router::<TestClient>(config, "/event_handler", Caches::default())
             .await
             .unwrap();
 